@@ -0,0 +1,232 @@
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{space0, space1, u32},
+    combinator::{all_consuming, map, value},
+    multi::separated_list1,
+    sequence::{delimited, separated_pair},
+    IResult,
+};
+use std::{fmt, str::FromStr};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Colour {
+    Red,
+    Green,
+    Blue,
+}
+
+impl fmt::Display for Colour {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Colour::Red => "red",
+            Colour::Green => "green",
+            Colour::Blue => "blue",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Draw {
+    pub count: u32,
+    pub colour: Colour,
+}
+
+impl fmt::Display for Draw {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.count, self.colour)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Round {
+    pub draws: Vec<Draw>,
+}
+
+impl Round {
+    pub fn count(&self, colour: Colour) -> u32 {
+        self.draws
+            .iter()
+            .filter(|x| x.colour == colour)
+            .map(|x| x.count)
+            .sum()
+    }
+
+    pub fn red(&self) -> u32 {
+        self.count(Colour::Red)
+    }
+
+    pub fn green(&self) -> u32 {
+        self.count(Colour::Green)
+    }
+
+    pub fn blue(&self) -> u32 {
+        self.count(Colour::Blue)
+    }
+
+    pub fn is_valid(&self, other: Bag) -> bool {
+        self.red() <= other.red && self.green() <= other.green && self.blue() <= other.blue
+    }
+}
+
+impl fmt::Display for Round {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, d) in self.draws.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{d}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Game {
+    pub id: u32,
+    pub rounds: Vec<Round>,
+}
+
+impl Game {
+    pub fn is_valid(&self, other: Bag) -> bool {
+        self.rounds.iter().all(|x| x.is_valid(other))
+    }
+
+    pub fn get_power(&self) -> u32 {
+        let min_red = self.rounds.iter().map(|x| x.red()).max().unwrap_or(0);
+        let min_green = self.rounds.iter().map(|x| x.green()).max().unwrap_or(0);
+        let min_blue = self.rounds.iter().map(|x| x.blue()).max().unwrap_or(0);
+        min_red * min_green * min_blue
+    }
+}
+
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Game {}: ", self.id)?;
+        for (i, r) in self.rounds.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{r}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Game {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        all_consuming(parse_game)(s.trim())
+            .map(|(_, game)| game)
+            .map_err(|e| format!("Invalid game '{s}': {e}"))
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct Bag {
+    pub blue: u32,
+    pub green: u32,
+    pub red: u32,
+}
+
+fn parse_colour(input: &str) -> IResult<&str, Colour> {
+    alt((
+        value(Colour::Red, tag("red")),
+        value(Colour::Green, tag("green")),
+        value(Colour::Blue, tag("blue")),
+    ))(input)
+}
+
+fn parse_draw(input: &str) -> IResult<&str, Draw> {
+    map(
+        separated_pair(u32, space1, parse_colour),
+        |(count, colour)| Draw { count, colour },
+    )(input)
+}
+
+fn parse_round(input: &str) -> IResult<&str, Round> {
+    map(
+        separated_list1(delimited(space0, tag(","), space0), parse_draw),
+        |draws| Round { draws },
+    )(input)
+}
+
+pub fn parse_game(input: &str) -> IResult<&str, Game> {
+    map(
+        separated_pair(
+            delimited(tag("Game "), u32, tag(":")),
+            space0,
+            separated_list1(delimited(space0, tag(";"), space0), parse_round),
+        ),
+        |(id, rounds)| Game { id, rounds },
+    )(input)
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Game>, String> {
+    input
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.parse())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CANONICAL: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red";
+
+    #[test]
+    fn test_parse_keeps_order() {
+        let game: Game = "Game 7: 2 green, 1 red; 4 blue".parse().unwrap();
+        assert_eq!(game.id, 7);
+        assert_eq!(
+            game.rounds[0].draws,
+            vec![
+                Draw {
+                    count: 2,
+                    colour: Colour::Green
+                },
+                Draw {
+                    count: 1,
+                    colour: Colour::Red
+                },
+            ]
+        );
+        assert_eq!(game.rounds[1].blue(), 4);
+        assert_eq!(game.rounds[1].red(), 0);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for l in CANONICAL.lines() {
+            let game: Game = l.parse().unwrap();
+            assert_eq!(game.to_string(), l);
+            assert_eq!(game.to_string().parse::<Game>().unwrap(), game);
+        }
+    }
+
+    #[test]
+    fn test_normalise() {
+        let game: Game = "  Game 12:3 red,5 blue ;  1 green".parse().unwrap();
+        assert_eq!(game.to_string(), "Game 12: 3 red, 5 blue; 1 green");
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!("Game 1: 3 purple".parse::<Game>().is_err());
+        assert!("Game x: 3 red".parse::<Game>().is_err());
+        assert!("Game 1: 3 red; ".parse::<Game>().is_err());
+    }
+
+    #[test]
+    fn test_parse_input_errors() {
+        let games = parse_input("Game 1: 3 blue\n\nGame 2: 1 red, 2 green").unwrap();
+        assert_eq!(games.len(), 2);
+        assert!(parse_input("Game 1: 3 blue\nGame 2: 1 purple").is_err());
+    }
+}
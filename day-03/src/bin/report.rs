@@ -0,0 +1,25 @@
+use day_03::{
+    report::{render_svg, totals_by_symbol},
+    rules::GearRule,
+    schematic::parse_input,
+};
+use std::{env, fs};
+
+fn read_input() -> String {
+    let input: String = fs::read_to_string("src/bin/input.txt").expect("Expected to read the file");
+    input
+}
+
+fn main() -> Result<(), String> {
+    let input: String = read_input();
+    let schematic = parse_input(input.as_str())?;
+    for (ch, sum) in totals_by_symbol(&schematic) {
+        println!("{ch}\t{sum}")
+    }
+    // Optional output path for the rendered schematic, e.g. `report schematic.svg`
+    if let Some(path) = env::args().nth(1) {
+        let svg = render_svg(&schematic, &GearRule::default());
+        fs::write(&path, svg).expect("Expected to write the svg file");
+    }
+    Ok(())
+}
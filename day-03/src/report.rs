@@ -0,0 +1,156 @@
+use crate::{rules::GearRule, schematic::Schematic};
+use std::{collections::BTreeMap, fmt::Write};
+
+const CELL: i32 = 12;
+const PART_COLOUR: &str = "#2e7d32";
+const UNATTACHED_COLOUR: &str = "#c62828";
+const GEAR_COLOUR: &str = "#ef6c00";
+const SYMBOL_COLOUR: &str = "#1565c0";
+
+// A number touching several symbols with the same character is counted once for that character.
+pub fn totals_by_symbol(schematic: &Schematic) -> BTreeMap<char, u64> {
+    let mut totals = BTreeMap::new();
+    for n in schematic.numbers.iter() {
+        let mut chars = schematic
+            .symbols_adjacent_to(n.id)
+            .iter()
+            .map(|s| s.ch)
+            .collect::<Vec<_>>();
+        chars.sort();
+        chars.dedup();
+        for ch in chars {
+            *totals.entry(ch).or_insert(0) += n.value as u64;
+        }
+    }
+    totals
+}
+
+fn escape(ch: char) -> String {
+    match ch {
+        '&' => "&amp;".to_string(),
+        '<' => "&lt;".to_string(),
+        '>' => "&gt;".to_string(),
+        _ => ch.to_string(),
+    }
+}
+
+fn write_text(svg: &mut String, x: i32, y: i32, text: &str, colour: &str) {
+    writeln!(
+        svg,
+        r#"<text x="{}" y="{}" fill="{}">{}</text>"#,
+        x * CELL + CELL / 2,
+        y * CELL + CELL - 2,
+        colour,
+        text
+    )
+    .unwrap();
+}
+
+pub fn render_svg(schematic: &Schematic, gear_rule: &GearRule) -> String {
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" font-family="monospace" font-size="{}" text-anchor="middle">"#,
+        schematic.width * CELL,
+        schematic.height * CELL,
+        CELL - 2
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        r##"<rect width="100%" height="100%" fill="#fafafa"/>"##
+    )
+    .unwrap();
+
+    for n in schematic.numbers.iter() {
+        let colour = if schematic.is_part(n.id) {
+            PART_COLOUR
+        } else {
+            UNATTACHED_COLOUR
+        };
+        writeln!(
+            svg,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}" fill-opacity="0.15"/>"#,
+            n.xmin * CELL,
+            n.y * CELL,
+            (n.xmax - n.xmin + 1) * CELL,
+            CELL,
+            colour
+        )
+        .unwrap();
+        for (x, d) in (n.xmin..=n.xmax).zip(n.digits.chars()) {
+            write_text(&mut svg, x, n.y, &d.to_string(), colour);
+        }
+    }
+
+    let gears = gear_rule.gears(schematic);
+    for s in schematic.symbols.iter() {
+        let colour = if gears.iter().any(|g| g.id == s.id) {
+            writeln!(
+                svg,
+                r#"<circle cx="{}" cy="{}" r="{}" fill="{}" fill-opacity="0.3"/>"#,
+                s.x * CELL + CELL / 2,
+                s.y * CELL + CELL / 2,
+                CELL / 2,
+                GEAR_COLOUR
+            )
+            .unwrap();
+            GEAR_COLOUR
+        } else {
+            SYMBOL_COLOUR
+        };
+        write_text(&mut svg, s.x, s.y, &escape(s.ch), colour);
+    }
+    writeln!(svg, "</svg>").unwrap();
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schematic::parse_input;
+
+    const INPUT: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    #[test]
+    fn test_totals_by_symbol() {
+        let totals = totals_by_symbol(&parse_input(INPUT).unwrap());
+        assert_eq!(totals[&'*'], 467 + 35 + 617 + 755 + 598);
+        assert_eq!(totals[&'#'], 633);
+        assert_eq!(totals[&'+'], 592);
+        assert_eq!(totals[&'$'], 664);
+        assert_eq!(totals.values().sum::<u64>(), 4361);
+    }
+
+    #[test]
+    fn test_render_svg() {
+        let svg = render_svg(&parse_input("1&.\n..*\n.22").unwrap(), &GearRule::default());
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert!(svg.contains("&amp;"));
+        assert_eq!(svg.matches(PART_COLOUR).count(), 2 + 3);
+        assert_eq!(svg.matches(UNATTACHED_COLOUR).count(), 0);
+        assert_eq!(svg.matches(GEAR_COLOUR).count(), 0);
+        let svg = render_svg(&parse_input("1.2\n.*.\n...").unwrap(), &GearRule::default());
+        assert_eq!(svg.matches(GEAR_COLOUR).count(), 2);
+        // Leading zeros are drawn in their own cells
+        let svg = render_svg(&parse_input("007*").unwrap(), &GearRule::default());
+        for x in 0..3 {
+            let digit = if x == 2 { '7' } else { '0' };
+            let text = format!(
+                r#"<text x="{}" y="10" fill="{PART_COLOUR}">{digit}</text>"#,
+                x * CELL + CELL / 2
+            );
+            assert!(svg.contains(&text), "{svg}");
+        }
+    }
+}
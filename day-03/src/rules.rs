@@ -0,0 +1,218 @@
+use crate::schematic::{Schematic, Symbol};
+use std::{fmt, str::FromStr};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Aggregation {
+    Product,
+    Sum,
+    Max,
+}
+
+impl Aggregation {
+    // `None` when the result does not fit in a u64
+    pub fn apply(&self, mut values: impl Iterator<Item = u64>) -> Option<u64> {
+        match self {
+            // A symbol with no numbers around it has nothing to multiply, not a ratio of 1
+            Aggregation::Product => {
+                let mut values = values.peekable();
+                if values.peek().is_none() {
+                    Some(0)
+                } else {
+                    values.try_fold(1_u64, |acc, x| acc.checked_mul(x))
+                }
+            }
+            Aggregation::Sum => values.try_fold(0_u64, |acc, x| acc.checked_add(x)),
+            Aggregation::Max => Some(values.max().unwrap_or(0)),
+        }
+    }
+}
+
+impl fmt::Display for Aggregation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Aggregation::Product => "product",
+            Aggregation::Sum => "sum",
+            Aggregation::Max => "max",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for Aggregation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "product" => Ok(Aggregation::Product),
+            "sum" => Ok(Aggregation::Sum),
+            "max" => Ok(Aggregation::Max),
+            _ => Err(format!("Unknown aggregation '{s}'")),
+        }
+    }
+}
+
+// A rule is written as `<symbols>:<neighbours>:<aggregation>`, e.g. `*:2:product`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GearRule {
+    pub symbols: Vec<char>,
+    pub neighbours: usize,
+    pub aggregation: Aggregation,
+}
+
+impl GearRule {
+    pub fn new(symbols: &[char], neighbours: usize, aggregation: Aggregation) -> Self {
+        GearRule {
+            symbols: symbols.to_vec(),
+            neighbours,
+            aggregation,
+        }
+    }
+
+    pub fn gears<'a>(&self, schematic: &'a Schematic) -> Vec<&'a Symbol> {
+        schematic
+            .symbols_with_neighbours(self.neighbours)
+            .into_iter()
+            .filter(|s| self.symbols.contains(&s.ch))
+            .collect()
+    }
+
+    pub fn evaluate(&self, schematic: &Schematic) -> Result<u64, String> {
+        self.gears(schematic)
+            .iter()
+            .map(|g| {
+                self.aggregation.apply(
+                    schematic
+                        .numbers_adjacent_to(g.id)
+                        .iter()
+                        .map(|n| n.value as u64),
+                )
+            })
+            .try_fold(0_u64, |acc, x| acc.checked_add(x?))
+            .ok_or_else(|| format!("Total for gear rule '{self}' does not fit in u64"))
+    }
+}
+
+impl Default for GearRule {
+    fn default() -> Self {
+        GearRule::new(&['*'], 2, Aggregation::Product)
+    }
+}
+
+impl fmt::Display for GearRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let symbols = String::from_iter(&self.symbols);
+        write!(f, "{}:{}:{}", symbols, self.neighbours, self.aggregation)
+    }
+}
+
+impl FromStr for GearRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split(':').collect();
+        let [symbols, neighbours, aggregation] = parts[..] else {
+            return Err(format!(
+                "Expected '<symbols>:<neighbours>:<aggregation>', got '{s}'"
+            ));
+        };
+        if symbols.is_empty() {
+            return Err(format!("No gear symbols in '{s}'"));
+        }
+        let neighbours = match neighbours.parse() {
+            Ok(n) if n >= 1 => n,
+            _ => return Err(format!("Invalid neighbour count '{neighbours}'")),
+        };
+        Ok(GearRule {
+            symbols: symbols.chars().collect(),
+            neighbours,
+            aggregation: aggregation.parse()?,
+        })
+    }
+}
+
+pub fn evaluate_rules(
+    schematic: &Schematic,
+    rules: &[GearRule],
+) -> Result<Vec<(String, u64)>, String> {
+    rules
+        .iter()
+        .map(|r| Ok((r.to_string(), r.evaluate(schematic)?)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schematic::parse_input;
+
+    const INPUT: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    #[test]
+    fn test_default_rule() {
+        let schematic = parse_input(INPUT).unwrap();
+        assert_eq!(GearRule::default().evaluate(&schematic), Ok(467835));
+    }
+
+    #[test]
+    fn test_rule_sets() {
+        let schematic = parse_input(INPUT).unwrap();
+        let rules = vec![
+            "*:2:sum".parse().unwrap(),
+            "*:1:max".parse().unwrap(),
+            "#$+:1:product".parse().unwrap(),
+        ];
+        let report = evaluate_rules(&schematic, &rules).unwrap();
+        assert_eq!(report[0], ("*:2:sum".to_string(), 467 + 35 + 755 + 598));
+        assert_eq!(report[1], ("*:1:max".to_string(), 617));
+        assert_eq!(report[2], ("#$+:1:product".to_string(), 633 + 664 + 592));
+    }
+
+    #[test]
+    fn test_parse_rule() {
+        assert_eq!(
+            "*:2:product".parse::<GearRule>().unwrap(),
+            GearRule::default()
+        );
+        assert!("*:2".parse::<GearRule>().is_err());
+        assert!("*:x:sum".parse::<GearRule>().is_err());
+        assert!("*:2:mean".parse::<GearRule>().is_err());
+        assert!(":2:sum".parse::<GearRule>().is_err());
+        assert!("*:0:product".parse::<GearRule>().is_err());
+    }
+
+    #[test]
+    fn test_empty_product() {
+        assert_eq!(Aggregation::Product.apply(std::iter::empty()), Some(0));
+        let rule = GearRule::new(&['*'], 0, Aggregation::Product);
+        assert_eq!(rule.evaluate(&parse_input("*..\n...\n..*").unwrap()), Ok(0));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let four =
+            parse_input("4294967295.4294967295\n..........*..........\n4294967295.4294967295")
+                .unwrap();
+        let rule = |x: &str| x.parse::<GearRule>().unwrap();
+        assert!(rule("*:4:product").evaluate(&four).is_err());
+        assert_eq!(rule("*:4:sum").evaluate(&four), Ok(4 * u32::MAX as u64));
+        // Each product fits, their sum does not
+        let two =
+            parse_input("4294967295*4294967295\n.....................\n4294967295*4294967295")
+                .unwrap();
+        let products = rule("*:2:product");
+        assert!(products.evaluate(&two).is_err());
+        assert_eq!(
+            products.evaluate(&parse_input("4294967295*4294967295").unwrap()),
+            Ok(u32::MAX as u64 * u32::MAX as u64)
+        );
+    }
+}
@@ -0,0 +1,283 @@
+use std::collections::HashMap;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Number {
+    pub id: usize,
+    pub y: i32,
+    pub xmin: i32,
+    pub xmax: i32,
+    pub value: u32,
+    // As written, including any leading zeros
+    pub digits: String,
+}
+
+impl Number {
+    pub fn get_neighbours(&self) -> Vec<(i32, i32)> {
+        let mut neighbours = Vec::new();
+        for y in self.y - 1..=self.y + 1 {
+            for x in self.xmin - 1..=self.xmax + 1 {
+                if y != self.y || x < self.xmin || x > self.xmax {
+                    neighbours.push((x, y));
+                }
+            }
+        }
+        neighbours
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Symbol {
+    pub id: usize,
+    pub x: i32,
+    pub y: i32,
+    pub ch: char,
+}
+
+impl Symbol {
+    pub fn get_neighbours(&self) -> Vec<(i32, i32)> {
+        let mut neighbours = Vec::new();
+        for y in self.y - 1..=self.y + 1 {
+            for x in self.x - 1..=self.x + 1 {
+                if y != self.y || x != self.x {
+                    neighbours.push((x, y));
+                }
+            }
+        }
+        neighbours
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Cell {
+    Number(usize),
+    Symbol(usize),
+}
+
+pub struct Schematic {
+    pub numbers: Vec<Number>,
+    pub symbols: Vec<Symbol>,
+    pub width: i32,
+    pub height: i32,
+    cells: HashMap<(i32, i32), Cell>,
+    symbol_numbers: Vec<Vec<usize>>,
+    number_symbols: Vec<Vec<usize>>,
+    by_degree: HashMap<usize, Vec<usize>>,
+}
+
+impl Schematic {
+    pub fn new(numbers: Vec<Number>, symbols: Vec<Symbol>, width: i32, height: i32) -> Self {
+        let mut cells = HashMap::new();
+        for n in numbers.iter() {
+            for x in n.xmin..=n.xmax {
+                cells.insert((x, n.y), Cell::Number(n.id));
+            }
+        }
+        for s in symbols.iter() {
+            cells.insert((s.x, s.y), Cell::Symbol(s.id));
+        }
+
+        let mut symbol_numbers = vec![Vec::new(); symbols.len()];
+        let mut number_symbols = vec![Vec::new(); numbers.len()];
+        for s in symbols.iter() {
+            for p in s.get_neighbours() {
+                if let Some(Cell::Number(id)) = cells.get(&p) {
+                    if !symbol_numbers[s.id].contains(id) {
+                        symbol_numbers[s.id].push(*id);
+                        number_symbols[*id].push(s.id);
+                    }
+                }
+            }
+        }
+        for v in symbol_numbers.iter_mut().chain(number_symbols.iter_mut()) {
+            v.sort();
+        }
+
+        let mut by_degree: HashMap<usize, Vec<usize>> = HashMap::new();
+        for (id, v) in symbol_numbers.iter().enumerate() {
+            by_degree.entry(v.len()).or_default().push(id);
+        }
+
+        Schematic {
+            numbers,
+            symbols,
+            width,
+            height,
+            cells,
+            symbol_numbers,
+            number_symbols,
+            by_degree,
+        }
+    }
+
+    pub fn get_cell(&self, x: i32, y: i32) -> Option<Cell> {
+        self.cells.get(&(x, y)).copied()
+    }
+
+    pub fn numbers_adjacent_to(&self, symbol_id: usize) -> Vec<&Number> {
+        self.symbol_numbers[symbol_id]
+            .iter()
+            .map(|id| &self.numbers[*id])
+            .collect()
+    }
+
+    pub fn symbols_adjacent_to(&self, number_id: usize) -> Vec<&Symbol> {
+        self.number_symbols[number_id]
+            .iter()
+            .map(|id| &self.symbols[*id])
+            .collect()
+    }
+
+    pub fn is_part(&self, number_id: usize) -> bool {
+        !self.number_symbols[number_id].is_empty()
+    }
+
+    pub fn symbols_with_neighbours(&self, k: usize) -> Vec<&Symbol> {
+        self.by_degree.get(&k).map_or(Vec::new(), |ids| {
+            ids.iter().map(|id| &self.symbols[*id]).collect()
+        })
+    }
+}
+
+fn parse_number(id: usize, y: i32, xmax: i32, digits: Vec<char>) -> Result<Number, String> {
+    let digits = String::from_iter(digits);
+    let value = digits
+        .parse()
+        .map_err(|_| format!("Part number {digits} does not fit in u32"))?;
+    Ok(Number {
+        id,
+        y,
+        xmin: xmax + 1 - digits.len() as i32,
+        xmax,
+        value,
+        digits,
+    })
+}
+
+pub fn parse_input(input: &str) -> Result<Schematic, String> {
+    let mut numbers = Vec::new();
+    let mut symbols = Vec::new();
+    let mut width = 0;
+    let mut height = 0;
+    // Only trailing whitespace is dropped, so leading cells keep their x coordinate
+    for (y, l) in input.lines().map(|l| l.trim_end()).enumerate() {
+        let maxline = l.chars().count() as i32;
+        width = width.max(maxline);
+        height = y as i32 + 1;
+        let mut number: Vec<char> = Vec::new();
+        for (x, i) in l.chars().enumerate() {
+            match i {
+                '0'..='9' => number.push(i),
+                _ => {
+                    if !number.is_empty() {
+                        numbers.push(parse_number(
+                            numbers.len(),
+                            y as i32,
+                            x as i32 - 1_i32,
+                            number,
+                        )?);
+                        number = Vec::new();
+                    }
+                    if i != '.' && !i.is_whitespace() {
+                        symbols.push(Symbol {
+                            id: symbols.len(),
+                            x: x as i32,
+                            y: y as i32,
+                            ch: i,
+                        });
+                    }
+                }
+            }
+        }
+        if !number.is_empty() {
+            numbers.push(parse_number(
+                numbers.len(),
+                y as i32,
+                maxline - 1_i32,
+                number,
+            )?);
+        }
+    }
+    Ok(Schematic::new(numbers, symbols, width, height))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    #[test]
+    fn test_parse() {
+        let schematic = parse_input(INPUT).unwrap();
+        assert_eq!(schematic.numbers.len(), 10);
+        assert_eq!(schematic.symbols.len(), 6);
+        assert_eq!(schematic.get_cell(1, 0), Some(Cell::Number(0)));
+        assert_eq!(schematic.get_cell(3, 1), Some(Cell::Symbol(0)));
+        assert_eq!(schematic.get_cell(0, 1), None);
+    }
+
+    #[test]
+    fn test_adjacency() {
+        let schematic = parse_input(INPUT).unwrap();
+        let values = schematic
+            .numbers_adjacent_to(0)
+            .iter()
+            .map(|n| n.value)
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![467, 35]);
+        let symbols = schematic.symbols_adjacent_to(4);
+        assert_eq!(symbols.len(), 1);
+        assert_eq!(symbols[0].ch, '*');
+        assert!(!schematic.is_part(1));
+        assert!(!schematic.is_part(5));
+    }
+
+    #[test]
+    fn test_symbols_with_neighbours() {
+        let schematic = parse_input(INPUT).unwrap();
+        let stars = |k| {
+            schematic
+                .symbols_with_neighbours(k)
+                .iter()
+                .filter(|s| s.ch == '*')
+                .count()
+        };
+        assert_eq!(stars(2), 2);
+        assert_eq!(stars(1), 1);
+        assert_eq!(schematic.symbols_with_neighbours(3).len(), 0);
+    }
+
+    #[test]
+    fn test_leading_whitespace() {
+        let schematic = parse_input("  12\r\n *..\r\n").unwrap();
+        assert_eq!(schematic.width, 4);
+        assert_eq!(schematic.symbols.len(), 1);
+        assert_eq!(schematic.symbols[0].x, 1);
+        assert_eq!(schematic.numbers[0].xmin, 2);
+        assert!(schematic.is_part(0));
+        let schematic = parse_input("   12\n*").unwrap();
+        assert!(!schematic.is_part(0));
+    }
+
+    #[test]
+    fn test_number_too_large() {
+        assert_eq!(
+            parse_input("99999999999*").map(|s| s.numbers.len()),
+            Err("Part number 99999999999 does not fit in u32".to_string())
+        );
+        assert!(parse_input("..\n.*4294967296").is_err());
+        assert_eq!(
+            parse_input("4294967295*").unwrap().numbers[0].value,
+            u32::MAX
+        );
+    }
+}
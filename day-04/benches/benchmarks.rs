@@ -0,0 +1,32 @@
+use day_04::card::{get_matches, get_matches_naive};
+use std::fmt::Write;
+
+fn main() {
+    divan::main();
+}
+
+// A single card with `n` winning numbers and `n` candidates, half of them matching.
+// No number repeats, since the naive matcher counts repeated candidates more than once.
+fn make_card(n: u32) -> String {
+    let mut line = String::from("Card 1:");
+    for i in 0..n {
+        write!(line, " {}", i * 3).unwrap();
+    }
+    line.push_str(" |");
+    for i in 0..n {
+        write!(line, " {}", i * 6).unwrap();
+    }
+    line
+}
+
+#[divan::bench(args = [10, 100, 1000, 5000])]
+fn bitset(bencher: divan::Bencher, n: u32) {
+    let line = make_card(n);
+    bencher.bench(|| get_matches(divan::black_box(&line)));
+}
+
+#[divan::bench(args = [10, 100, 1000, 5000])]
+fn naive(bencher: divan::Bencher, n: u32) {
+    let line = make_card(n);
+    bencher.bench(|| get_matches_naive(divan::black_box(&line)));
+}
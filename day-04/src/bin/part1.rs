@@ -1,54 +1,34 @@
-use itertools::Itertools;
-use std::fs;
+use day_04::{card::get_matches, scoring::Registry};
+use std::{env, fs};
 
 fn read_input() -> String {
     let input: String = fs::read_to_string("src/bin/input.txt").expect("Expected to read the file");
     input
 }
 
-fn get_matches(line: &str) -> u32 {
-    let l_card = line.split(':').collect_vec();
-    let mut l_iter = l_card.get(1).unwrap().split('|');
-    let winning = l_iter
-        .next()
-        .unwrap()
-        .split(' ')
-        .filter(|x| !x.is_empty())
-        .collect_vec();
-    let check = l_iter
-        .next()
-        .unwrap()
-        .split(' ')
-        .filter(|x| !x.is_empty())
-        .collect_vec();
-    let matches = check.iter().map(|x| winning.contains(x) as u32).sum();
-    matches
-}
-
-fn get_value(matches: u32) -> u32 {
-    match matches {
-        0 => 0,
-        _ => u32::pow(2, matches - 1),
-    }
-}
-
-fn run(input: String) -> u32 {
-    let sum: u32 = input
+fn run(input: String, scheme: &str) -> u128 {
+    let matches = input
         .lines()
         .map(get_matches)
-        .map(get_value)
-        .sum();
-    sum
+        .collect::<Result<Vec<_>, _>>()
+        .expect("Expected valid cards");
+    let scoring = Registry::default()
+        .get(scheme)
+        .expect("Expected a known scoring scheme");
+    scoring
+        .score(&matches)
+        .expect("Expected a valid card table")
 }
 
 fn main() {
     let input: String = read_input();
-    let sum = run(input);
+    // Optional scoring scheme, e.g. `part1 linear:2`
+    let scheme = env::args().nth(1).unwrap_or("doubling".to_string());
+    let sum = run(input, scheme.as_str());
     println!("{sum}")
 }
 
 #[cfg(test)]
-
 mod tests {
     use super::*;
     #[test]
@@ -59,6 +39,6 @@ Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
 Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
 Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
 Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
-        assert_eq!(run(input.to_string()), 13);
+        assert_eq!(run(input.to_string(), "doubling"), 13);
     }
 }
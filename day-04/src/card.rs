@@ -0,0 +1,187 @@
+use itertools::Itertools;
+
+// Numbers below this bound are stored in a bitset, anything above in a sorted vector
+const BITSET_RANGE: u32 = 256;
+const WORDS: usize = (BITSET_RANGE / 64) as usize;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NumberSet {
+    bits: [u64; WORDS],
+    large: Vec<u32>,
+}
+
+impl NumberSet {
+    pub fn insert(&mut self, x: u32) {
+        if x < BITSET_RANGE {
+            self.bits[(x / 64) as usize] |= 1 << (x % 64);
+        } else if let Err(pos) = self.large.binary_search(&x) {
+            self.large.insert(pos, x);
+        }
+    }
+
+    pub fn contains(&self, x: u32) -> bool {
+        if x < BITSET_RANGE {
+            self.bits[(x / 64) as usize] & (1 << (x % 64)) != 0
+        } else {
+            self.large.binary_search(&x).is_ok()
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits
+            .iter()
+            .map(|x| x.count_ones() as usize)
+            .sum::<usize>()
+            + self.large.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn intersection_len(&self, other: &NumberSet) -> u32 {
+        let small: u32 = self
+            .bits
+            .iter()
+            .zip(other.bits.iter())
+            .map(|(a, b)| (a & b).count_ones())
+            .sum();
+        let (mut i, mut j, mut large) = (0, 0, 0);
+        while i < self.large.len() && j < other.large.len() {
+            match self.large[i].cmp(&other.large[j]) {
+                std::cmp::Ordering::Less => i += 1,
+                std::cmp::Ordering::Greater => j += 1,
+                std::cmp::Ordering::Equal => {
+                    large += 1;
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+        small + large
+    }
+}
+
+impl FromIterator<u32> for NumberSet {
+    fn from_iter<I: IntoIterator<Item = u32>>(iter: I) -> Self {
+        let mut set = NumberSet::default();
+        for x in iter {
+            if x < BITSET_RANGE {
+                set.insert(x);
+            } else {
+                set.large.push(x);
+            }
+        }
+        set.large.sort_unstable();
+        set.large.dedup();
+        set
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Card {
+    pub id: u32,
+    pub winning: NumberSet,
+    pub numbers: NumberSet,
+}
+
+impl Card {
+    // Repeated numbers on one side count once
+    pub fn matches(&self) -> u32 {
+        self.winning.intersection_len(&self.numbers)
+    }
+}
+
+fn parse_numbers(input: &str) -> Result<NumberSet, String> {
+    input
+        .split(' ')
+        .filter(|x| !x.is_empty())
+        .map(|x| x.parse().map_err(|_| format!("Invalid card number '{x}'")))
+        .collect()
+}
+
+pub fn parse_card(line: &str) -> Result<Card, String> {
+    let (header, body) = line
+        .split_once(':')
+        .ok_or_else(|| format!("Expected 'Card N:' in '{line}'"))?;
+    let id = header.trim().trim_start_matches("Card").trim();
+    let id = id.parse().map_err(|_| format!("Invalid card id '{id}'"))?;
+    let (winning, numbers) = body
+        .split_once('|')
+        .ok_or_else(|| format!("Expected '|' in '{line}'"))?;
+    Ok(Card {
+        id,
+        winning: parse_numbers(winning)?,
+        numbers: parse_numbers(numbers)?,
+    })
+}
+
+pub fn get_matches(line: &str) -> Result<u32, String> {
+    parse_card(line).map(|card| card.matches())
+}
+
+// String based matching as used before the bitset, kept for benchmarks.
+// Unlike `Card::matches`, a repeated candidate number counts every time it appears.
+pub fn get_matches_naive(line: &str) -> u32 {
+    let l_card = line.split(':').collect_vec();
+    let mut l_iter = l_card.get(1).unwrap().split('|');
+    let winning = l_iter
+        .next()
+        .unwrap()
+        .split(' ')
+        .filter(|x| !x.is_empty())
+        .collect_vec();
+    let check = l_iter
+        .next()
+        .unwrap()
+        .split(' ')
+        .filter(|x| !x.is_empty())
+        .collect_vec();
+    let matches = check.iter().map(|x| winning.contains(x) as u32).sum();
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+    #[test]
+    fn test_matches() {
+        let bitset = INPUT.lines().map(|x| get_matches(x).unwrap()).collect_vec();
+        let naive = INPUT.lines().map(get_matches_naive).collect_vec();
+        assert_eq!(bitset, vec![4, 2, 2, 1, 0, 0]);
+        assert_eq!(bitset, naive);
+    }
+
+    #[test]
+    fn test_large_numbers() {
+        let card = parse_card("Card 12: 5 255 256 1000 99999 | 99999 4 256 255 70000").unwrap();
+        assert_eq!(card.id, 12);
+        assert_eq!(card.winning.len(), 5);
+        assert!(card.numbers.contains(70000));
+        assert!(!card.numbers.contains(1000));
+        assert_eq!(card.matches(), 3);
+    }
+
+    #[test]
+    fn test_invalid_cards() {
+        assert!(parse_card("Card 1: 1 x | 2").is_err());
+        assert!(parse_card("Card 1: 1 2").is_err());
+        assert!(parse_card("Card x: 1 | 2").is_err());
+        assert!(parse_card("1 | 2").is_err());
+    }
+
+    #[test]
+    fn test_repeated_candidates() {
+        let line = "Card 1: 5 7 | 5 5 8";
+        assert_eq!(get_matches(line), Ok(1));
+        assert_eq!(get_matches_naive(line), 2);
+    }
+}
@@ -0,0 +1,159 @@
+use std::fmt;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    // Wins past the last card are dropped and the card is recorded in `clamped`
+    Clamp,
+    // Wins past the last card abort the simulation
+    Report,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SimulationError {
+    WinsPastEnd {
+        card: usize,
+        wins: u32,
+        remaining: usize,
+    },
+    CopiesOverflow {
+        card: usize,
+    },
+    ScoreOverflow {
+        card: usize,
+    },
+}
+
+impl fmt::Display for SimulationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SimulationError::WinsPastEnd {
+                card,
+                wins,
+                remaining,
+            } => write!(
+                f,
+                "Card {card} wins {wins} copies but only {remaining} cards follow"
+            ),
+            SimulationError::CopiesOverflow { card } => {
+                write!(f, "Copy count overflows at card {card}")
+            }
+            SimulationError::ScoreOverflow { card } => {
+                write!(f, "Score overflows at card {card}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SimulationError {}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Simulation {
+    pub copies: Vec<u128>,
+    pub clamped: Vec<usize>,
+}
+
+impl Simulation {
+    // Card numbers in errors and `clamped` are 1-based, as on the cards
+    pub fn run(matches: &[u32], policy: OverflowPolicy) -> Result<Simulation, SimulationError> {
+        Simulation::run_capped(matches, policy, None)
+    }
+
+    // With a cap, no card ever holds more than `cap` copies
+    pub fn run_capped(
+        matches: &[u32],
+        policy: OverflowPolicy,
+        cap: Option<u128>,
+    ) -> Result<Simulation, SimulationError> {
+        let mut copies: Vec<u128> = vec![1; matches.len()];
+        let mut clamped = Vec::new();
+        for (i, wins) in matches.iter().enumerate() {
+            if let Some(cap) = cap {
+                copies[i] = copies[i].min(cap);
+            }
+            let remaining = matches.len() - i - 1;
+            let mut won = *wins as usize;
+            if won > remaining {
+                match policy {
+                    OverflowPolicy::Clamp => {
+                        clamped.push(i + 1);
+                        won = remaining;
+                    }
+                    OverflowPolicy::Report => {
+                        return Err(SimulationError::WinsPastEnd {
+                            card: i + 1,
+                            wins: *wins,
+                            remaining,
+                        })
+                    }
+                }
+            }
+            for j in 1..=won {
+                copies[i + j] = copies[i + j]
+                    .checked_add(copies[i])
+                    .ok_or(SimulationError::CopiesOverflow { card: i + j + 1 })?;
+            }
+        }
+        Ok(Simulation { copies, clamped })
+    }
+
+    pub fn total(&self) -> Result<u128, SimulationError> {
+        self.copies
+            .iter()
+            .enumerate()
+            .try_fold(0_u128, |acc, (i, x)| {
+                acc.checked_add(*x)
+                    .ok_or(SimulationError::CopiesOverflow { card: i + 1 })
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copies() {
+        let sim = Simulation::run(&[4, 2, 2, 1, 0, 0], OverflowPolicy::Report).unwrap();
+        assert_eq!(sim.copies, vec![1, 2, 4, 8, 14, 1]);
+        assert_eq!(sim.total().unwrap(), 30);
+        assert!(sim.clamped.is_empty());
+    }
+
+    #[test]
+    fn test_overflowing_wins() {
+        let matches = [1, 3, 1];
+        assert_eq!(
+            Simulation::run(&matches, OverflowPolicy::Report),
+            Err(SimulationError::WinsPastEnd {
+                card: 2,
+                wins: 3,
+                remaining: 1
+            })
+        );
+        let sim = Simulation::run(&matches, OverflowPolicy::Clamp).unwrap();
+        assert_eq!(sim.copies, vec![1, 2, 3]);
+        assert_eq!(sim.clamped, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_capped() {
+        let sim =
+            Simulation::run_capped(&[4, 2, 2, 1, 0, 0], OverflowPolicy::Report, Some(3)).unwrap();
+        assert_eq!(sim.copies, vec![1, 2, 3, 3, 3, 1]);
+    }
+
+    #[test]
+    fn test_wide_totals() {
+        // Every card wins all following cards, so copies double each step
+        let n = 100;
+        let matches = (0..n).map(|i| n - i - 1).collect::<Vec<u32>>();
+        let sim = Simulation::run(&matches, OverflowPolicy::Report).unwrap();
+        assert_eq!(sim.copies[99], 1 << 99);
+        assert_eq!(sim.total().unwrap(), (1 << 100) - 1);
+        let matches = (0..200).map(|i| 200 - i - 1).collect::<Vec<u32>>();
+        assert_eq!(
+            Simulation::run(&matches, OverflowPolicy::Report),
+            Err(SimulationError::CopiesOverflow { card: 129 })
+        );
+    }
+}
@@ -0,0 +1,3 @@
+pub mod card;
+pub mod engine;
+pub mod scoring;
@@ -0,0 +1,220 @@
+use crate::engine::{OverflowPolicy, Simulation, SimulationError};
+use std::collections::BTreeMap;
+
+pub trait Scoring {
+    fn name(&self) -> String;
+    fn score(&self, matches: &[u32]) -> Result<u128, SimulationError>;
+}
+
+// 1 point for the first match, doubled for every further match
+pub struct Doubling;
+
+impl Scoring for Doubling {
+    fn name(&self) -> String {
+        "doubling".to_string()
+    }
+
+    fn score(&self, matches: &[u32]) -> Result<u128, SimulationError> {
+        matches.iter().enumerate().try_fold(0_u128, |acc, (i, m)| {
+            let value = match m {
+                0 => Some(0),
+                _ => 1_u128.checked_shl(m - 1),
+            };
+            value
+                .and_then(|v| acc.checked_add(v))
+                .ok_or(SimulationError::ScoreOverflow { card: i + 1 })
+        })
+    }
+}
+
+// A fixed number of points per match
+pub struct Linear {
+    pub points: u128,
+}
+
+impl Scoring for Linear {
+    fn name(&self) -> String {
+        "linear".to_string()
+    }
+
+    fn score(&self, matches: &[u32]) -> Result<u128, SimulationError> {
+        matches.iter().enumerate().try_fold(0_u128, |acc, (i, m)| {
+            (*m as u128)
+                .checked_mul(self.points)
+                .and_then(|v| acc.checked_add(v))
+                .ok_or(SimulationError::ScoreOverflow { card: i + 1 })
+        })
+    }
+}
+
+// Matches win copies of the following cards, the score is the number of cards held
+pub struct CascadingCopies {
+    pub policy: OverflowPolicy,
+}
+
+impl Scoring for CascadingCopies {
+    fn name(&self) -> String {
+        "cascading-copies".to_string()
+    }
+
+    fn score(&self, matches: &[u32]) -> Result<u128, SimulationError> {
+        Simulation::run(matches, self.policy)?.total()
+    }
+}
+
+// Like `CascadingCopies`, but no card is held more than `cap` times
+pub struct CappedCopies {
+    pub policy: OverflowPolicy,
+    pub cap: u128,
+}
+
+impl Scoring for CappedCopies {
+    fn name(&self) -> String {
+        format!("capped-copies:{}", self.cap)
+    }
+
+    fn score(&self, matches: &[u32]) -> Result<u128, SimulationError> {
+        Simulation::run_capped(matches, self.policy, Some(self.cap))?.total()
+    }
+}
+
+type Factory = Box<dyn Fn(Option<&str>) -> Result<Box<dyn Scoring>, String>>;
+
+// Schemes are looked up as `<name>` or `<name>:<argument>`, e.g. `capped-copies:100`
+pub struct Registry {
+    factories: BTreeMap<String, Factory>,
+}
+
+impl Registry {
+    pub fn empty() -> Self {
+        Registry {
+            factories: BTreeMap::new(),
+        }
+    }
+
+    pub fn register(
+        &mut self,
+        name: &str,
+        factory: impl Fn(Option<&str>) -> Result<Box<dyn Scoring>, String> + 'static,
+    ) {
+        self.factories.insert(name.to_string(), Box::new(factory));
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.factories.keys().map(|x| x.as_str()).collect()
+    }
+
+    pub fn get(&self, spec: &str) -> Result<Box<dyn Scoring>, String> {
+        let (name, arg) = match spec.split_once(':') {
+            Some((name, arg)) => (name, Some(arg)),
+            None => (spec, None),
+        };
+        let factory = self
+            .factories
+            .get(name)
+            .ok_or(format!("Unknown scoring scheme '{name}'"))?;
+        factory(arg)
+    }
+}
+
+fn no_arg(name: &str, arg: Option<&str>) -> Result<(), String> {
+    match arg {
+        None => Ok(()),
+        Some(x) => Err(format!("{name} takes no argument, got '{x}'")),
+    }
+}
+
+fn parse_arg(arg: Option<&str>, default: u128) -> Result<u128, String> {
+    match arg {
+        None => Ok(default),
+        Some(x) => x.parse().map_err(|_| format!("Invalid argument '{x}'")),
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        let mut registry = Registry::empty();
+        registry.register("doubling", |arg| {
+            no_arg("doubling", arg)?;
+            Ok(Box::new(Doubling))
+        });
+        registry.register("linear", |arg| {
+            Ok(Box::new(Linear {
+                points: parse_arg(arg, 1)?,
+            }))
+        });
+        registry.register("cascading-copies", |arg| {
+            no_arg("cascading-copies", arg)?;
+            Ok(Box::new(CascadingCopies {
+                policy: OverflowPolicy::Report,
+            }))
+        });
+        registry.register("capped-copies", |arg| {
+            let cap = arg.ok_or("capped-copies needs a cap, e.g. 'capped-copies:100'")?;
+            Ok(Box::new(CappedCopies {
+                policy: OverflowPolicy::Report,
+                cap: parse_arg(Some(cap), 0)?,
+            }))
+        });
+        registry
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MATCHES: [u32; 6] = [4, 2, 2, 1, 0, 0];
+
+    #[test]
+    fn test_builtin_schemes() {
+        let registry = Registry::default();
+        let score = |spec: &str| registry.get(spec).unwrap().score(&MATCHES).unwrap();
+        assert_eq!(score("doubling"), 13);
+        assert_eq!(score("linear"), 9);
+        assert_eq!(score("linear:3"), 27);
+        assert_eq!(score("cascading-copies"), 30);
+        assert_eq!(score("capped-copies:3"), 13);
+        assert!(registry.get("capped-copies").is_err());
+        assert!(registry.get("linear:x").is_err());
+        assert!(registry.get("unknown").is_err());
+        assert!(registry.get("doubling:7").is_err());
+        assert!(registry.get("cascading-copies:7").is_err());
+    }
+
+    #[test]
+    fn test_score_overflow() {
+        let registry = Registry::default();
+        let linear = registry.get(&format!("linear:{}", u128::MAX)).unwrap();
+        assert_eq!(linear.score(&[0, 1]), Ok(u128::MAX));
+        assert_eq!(
+            linear.score(&[0, 2]),
+            Err(SimulationError::ScoreOverflow { card: 2 })
+        );
+        assert_eq!(
+            linear.score(&[1, 1]),
+            Err(SimulationError::ScoreOverflow { card: 2 })
+        );
+        assert_eq!(
+            Doubling.score(&[1, 129]),
+            Err(SimulationError::ScoreOverflow { card: 2 })
+        );
+    }
+
+    #[test]
+    fn test_custom_scheme() {
+        struct Wins;
+        impl Scoring for Wins {
+            fn name(&self) -> String {
+                "wins".to_string()
+            }
+            fn score(&self, matches: &[u32]) -> Result<u128, SimulationError> {
+                Ok(matches.iter().filter(|m| **m > 0).count() as u128)
+            }
+        }
+        let mut registry = Registry::default();
+        registry.register("wins", |_| Ok(Box::new(Wins)));
+        assert!(registry.names().contains(&"wins"));
+        assert_eq!(registry.get("wins").unwrap().score(&MATCHES).unwrap(), 4);
+    }
+}
@@ -0,0 +1,470 @@
+use crate::interval::{IntervalMap, Range, Segment};
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alpha1, i64, line_ending, multispace1, space1},
+    combinator::all_consuming,
+    multi::separated_list1,
+    sequence::{preceded, separated_pair, terminated, tuple},
+    IResult,
+};
+use std::{collections::BTreeSet, fmt};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CategoryMap {
+    pub src: String,
+    pub dst: String,
+    pub map: IntervalMap,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Almanac {
+    pub seeds: Vec<i64>,
+    pub maps: Vec<CategoryMap>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AlmanacError {
+    Syntax(String),
+    Overlap {
+        src: String,
+        dst: String,
+        message: String,
+    },
+    InvalidMapping {
+        src: String,
+        dst: String,
+        mapping: [i64; 3],
+    },
+    OddSeedCount(usize),
+    InvalidSeedRange {
+        start: i64,
+        len: i64,
+    },
+}
+
+impl fmt::Display for AlmanacError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AlmanacError::Syntax(message) => write!(f, "Invalid almanac: {message}"),
+            AlmanacError::Overlap { src, dst, message } => {
+                write!(f, "{src}-to-{dst} map: {message}")
+            }
+            AlmanacError::InvalidMapping { src, dst, mapping } => {
+                let [dst_start, src_start, len] = mapping;
+                write!(
+                    f,
+                    "{src}-to-{dst} map: mapping '{dst_start} {src_start} {len}' is out of range"
+                )
+            }
+            AlmanacError::OddSeedCount(count) => {
+                write!(
+                    f,
+                    "{count} seed values cannot be read as start/length pairs"
+                )
+            }
+            AlmanacError::InvalidSeedRange { start, len } => {
+                write!(f, "Seed range '{start} {len}' is out of range")
+            }
+        }
+    }
+}
+
+impl std::error::Error for AlmanacError {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RouteError {
+    UnknownCategory(String),
+    NoRoute { from: String, to: String },
+    Ambiguous { from: String, to: String },
+    Cycle(Vec<String>),
+}
+
+impl fmt::Display for RouteError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RouteError::UnknownCategory(name) => write!(f, "Unknown category '{name}'"),
+            RouteError::NoRoute { from, to } => write!(f, "No route from {from} to {to}"),
+            RouteError::Ambiguous { from, to } => {
+                write!(f, "More than one route from {from} to {to}")
+            }
+            RouteError::Cycle(path) => write!(f, "Cycle {}", path.join(" -> ")),
+        }
+    }
+}
+
+impl std::error::Error for RouteError {}
+
+impl Almanac {
+    pub fn categories(&self) -> BTreeSet<&str> {
+        self.maps
+            .iter()
+            .flat_map(|m| [m.src.as_str(), m.dst.as_str()])
+            .collect()
+    }
+
+    // Categories from which `to` can be reached
+    fn reaching<'a>(&'a self, to: &'a str) -> BTreeSet<&'a str> {
+        let mut reaching = BTreeSet::from([to]);
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for m in self.maps.iter() {
+                if reaching.contains(m.dst.as_str()) {
+                    changed |= reaching.insert(m.src.as_str());
+                }
+            }
+        }
+        reaching
+    }
+
+    // Only follows maps towards `to`, so any cycle found would give endless routes.
+    // Stops at the second route found, which is enough to call the route ambiguous.
+    fn find_routes<'a: 'n, 'n>(
+        &'a self,
+        node: &'n str,
+        to: &str,
+        reaching: &BTreeSet<&str>,
+        visited: &mut Vec<&'n str>,
+        path: &mut Vec<&'a CategoryMap>,
+        routes: &mut Vec<Vec<&'a CategoryMap>>,
+    ) -> Result<(), RouteError> {
+        if node == to {
+            routes.push(path.clone());
+            return Ok(());
+        }
+        visited.push(node);
+        for m in self
+            .maps
+            .iter()
+            .filter(|m| m.src == node && reaching.contains(m.dst.as_str()))
+        {
+            if visited.contains(&m.dst.as_str()) {
+                let start = visited.iter().position(|x| *x == m.dst).unwrap();
+                let mut cycle = visited[start..]
+                    .iter()
+                    .map(|x| x.to_string())
+                    .collect::<Vec<_>>();
+                cycle.push(m.dst.clone());
+                return Err(RouteError::Cycle(cycle));
+            }
+            path.push(m);
+            self.find_routes(&m.dst, to, reaching, visited, path, routes)?;
+            path.pop();
+            if routes.len() > 1 {
+                break;
+            }
+        }
+        visited.pop();
+        Ok(())
+    }
+
+    // The maps leading from one category to another, in the order they apply
+    pub fn route(&self, from: &str, to: &str) -> Result<Vec<&CategoryMap>, RouteError> {
+        let categories = self.categories();
+        for name in [from, to] {
+            if !categories.contains(name) {
+                return Err(RouteError::UnknownCategory(name.to_string()));
+            }
+        }
+        let reaching = self.reaching(to);
+        let mut routes = Vec::new();
+        self.find_routes(
+            from,
+            to,
+            &reaching,
+            &mut Vec::new(),
+            &mut Vec::new(),
+            &mut routes,
+        )?;
+        match routes.len() {
+            0 => Err(RouteError::NoRoute {
+                from: from.to_string(),
+                to: to.to_string(),
+            }),
+            1 => Ok(routes.pop().unwrap()),
+            _ => Err(RouteError::Ambiguous {
+                from: from.to_string(),
+                to: to.to_string(),
+            }),
+        }
+    }
+
+    // Every map on the route collapsed into one function
+    pub fn route_map(&self, from: &str, to: &str) -> Result<IntervalMap, RouteError> {
+        Ok(self
+            .route(from, to)?
+            .iter()
+            .fold(IntervalMap::default(), |acc, m| acc.compose(&m.map)))
+    }
+
+    pub fn chain(&self) -> Result<IntervalMap, RouteError> {
+        self.route_map("seed", "location")
+    }
+
+    // The seeds read as `start length` pairs
+    pub fn seed_ranges(&self) -> Result<Vec<Range>, AlmanacError> {
+        let pairs = self.seeds.chunks_exact(2);
+        if !pairs.remainder().is_empty() {
+            return Err(AlmanacError::OddSeedCount(self.seeds.len()));
+        }
+        pairs
+            .map(|x| {
+                Range::checked_from_len(x[0], x[1]).ok_or(AlmanacError::InvalidSeedRange {
+                    start: x[0],
+                    len: x[1],
+                })
+            })
+            .collect()
+    }
+}
+
+fn parse_seeds(input: &str) -> IResult<&str, Vec<i64>> {
+    preceded(tag("seeds: "), separated_list1(space1, i64))(input)
+}
+
+// `<dst_start> <src_start> <len>`, checked once the whole almanac has parsed
+fn parse_mapping(input: &str) -> IResult<&str, [i64; 3]> {
+    let (input, (dst_start, _, src_start, _, len)) = tuple((i64, space1, i64, space1, i64))(input)?;
+    Ok((input, [dst_start, src_start, len]))
+}
+
+fn parse_map(input: &str) -> IResult<&str, (&str, &str, Vec<[i64; 3]>)> {
+    let (input, (src, dst)) = terminated(
+        separated_pair(alpha1, tag("-to-"), alpha1),
+        tuple((tag(" map:"), line_ending)),
+    )(input)?;
+    let (input, segments) = separated_list1(line_ending, parse_mapping)(input)?;
+    Ok((input, (src, dst, segments)))
+}
+
+pub fn parse_input(input: &str) -> Result<Almanac, AlmanacError> {
+    let (_, (seeds, maps)) = all_consuming(separated_pair(
+        parse_seeds,
+        multispace1,
+        separated_list1(multispace1, parse_map),
+    ))(input.trim())
+    .map_err(|e| AlmanacError::Syntax(e.to_string()))?;
+    let maps = maps
+        .into_iter()
+        .map(|(src, dst, mappings)| {
+            let segments = mappings
+                .into_iter()
+                .map(|mapping| {
+                    let [dst_start, src_start, len] = mapping;
+                    Segment::checked_from_almanac(dst_start, src_start, len).ok_or_else(|| {
+                        AlmanacError::InvalidMapping {
+                            src: src.to_string(),
+                            dst: dst.to_string(),
+                            mapping,
+                        }
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let map = IntervalMap::new(segments).map_err(|message| AlmanacError::Overlap {
+                src: src.to_string(),
+                dst: dst.to_string(),
+                message,
+            })?;
+            Ok(CategoryMap {
+                src: src.to_string(),
+                dst: dst.to_string(),
+                map,
+            })
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(Almanac { seeds, maps })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interval::DOMAIN;
+
+    const INPUT: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
+    #[test]
+    fn test_chain() {
+        let almanac = parse_input(INPUT).unwrap();
+        assert_eq!(almanac.maps.len(), 7);
+        let chain = almanac.chain().unwrap();
+        let locations = almanac
+            .seeds
+            .iter()
+            .map(|x| chain.apply(*x))
+            .collect::<Vec<_>>();
+        assert_eq!(locations, vec![82, 43, 86, 35]);
+        assert!(chain
+            .preimage(&Range::from_len(35, 1))
+            .contains(&Range::from_len(13, 1)));
+    }
+
+    #[test]
+    fn test_any_order() {
+        let mut blocks = INPUT.split("\n\n").collect::<Vec<_>>();
+        blocks[1..].reverse();
+        let almanac = parse_input(blocks.join("\n\n").as_str()).unwrap();
+        assert_eq!(almanac.maps[0].src, "humidity");
+        assert_eq!(almanac.chain().unwrap().apply(79), 82);
+        let route = almanac.route("soil", "humidity").unwrap();
+        let names = route.iter().map(|m| m.dst.as_str()).collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec!["fertilizer", "water", "light", "temperature", "humidity"]
+        );
+        assert_eq!(almanac.route_map("soil", "humidity").unwrap().apply(81), 78);
+    }
+
+    #[test]
+    fn test_route_errors() {
+        let almanac = parse_input(INPUT).unwrap();
+        assert_eq!(
+            almanac.route("location", "seed"),
+            Err(RouteError::NoRoute {
+                from: "location".to_string(),
+                to: "seed".to_string()
+            })
+        );
+        assert_eq!(
+            almanac.route("seed", "moon"),
+            Err(RouteError::UnknownCategory("moon".to_string()))
+        );
+
+        let input = "seeds: 1\n\na-to-b map:\n0 0 1\n\nb-to-c map:\n0 0 1\n\nc-to-a map:\n0 0 1\n\nb-to-d map:\n0 0 1\n\nd-to-e map:\n0 0 1\n\ne-to-d map:\n0 0 1";
+        let almanac = parse_input(input).unwrap();
+        assert_eq!(almanac.route("a", "b").unwrap().len(), 1);
+        assert_eq!(almanac.route("d", "e").unwrap().len(), 1);
+        assert_eq!(
+            almanac.route("a", "d"),
+            Err(RouteError::Cycle(vec![
+                "a".to_string(),
+                "b".to_string(),
+                "c".to_string(),
+                "a".to_string()
+            ]))
+        );
+
+        let input = "seeds: 1\n\na-to-b map:\n0 0 1\n\nb-to-d map:\n0 0 1\n\na-to-c map:\n0 0 1\n\nc-to-d map:\n0 0 1";
+        let almanac = parse_input(input).unwrap();
+        assert_eq!(almanac.route("a", "b").unwrap().len(), 1);
+        assert_eq!(
+            almanac.route("a", "d"),
+            Err(RouteError::Ambiguous {
+                from: "a".to_string(),
+                to: "d".to_string(),
+            })
+        );
+
+        // 2^40 routes through a chain of diamonds; category names are letters only
+        let name = |prefix: &str, i: usize| format!("{prefix}{}", "x".repeat(i));
+        let mut input = "seeds: 1".to_string();
+        for i in 0..40 {
+            for side in ["a", "b"] {
+                let (node, next, side) = (name("n", i), name("n", i + 1), name(side, i));
+                input += &format!("\n\n{node}-to-{side} map:\n0 0 1");
+                input += &format!("\n\n{side}-to-{next} map:\n0 0 1");
+            }
+        }
+        let almanac = parse_input(&input).unwrap();
+        assert_eq!(
+            almanac.route("n", &name("n", 40)),
+            Err(RouteError::Ambiguous {
+                from: "n".to_string(),
+                to: name("n", 40),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let bad_segment = INPUT.replace("37 52 2", "37 52 x");
+        assert!(matches!(
+            parse_input(&bad_segment),
+            Err(AlmanacError::Syntax(_))
+        ));
+        let overlap = "seeds: 1\n\na-to-b map:\n0 0 5\n10 3 5";
+        assert!(matches!(
+            parse_input(overlap),
+            Err(AlmanacError::Overlap { .. })
+        ));
+        for mapping in [
+            "0 9223372036854775807 5",
+            "9223372036854775807 0 5",
+            "0 10 -5",
+            "5 3000000000000000000 10",
+        ] {
+            let input = format!("seeds: 1\n\na-to-b map:\n{mapping}");
+            assert!(matches!(
+                parse_input(&input),
+                Err(AlmanacError::InvalidMapping { .. })
+            ));
+        }
+        let crlf = INPUT.replace('\n', "\r\n");
+        assert_eq!(parse_input(&crlf), parse_input(INPUT));
+    }
+
+    #[test]
+    fn test_domain_edge() {
+        let input = format!(
+            "seeds: {0} {1}\n\nseed-to-location map:\n5 {0} 10",
+            DOMAIN.end - 10,
+            DOMAIN.end
+        );
+        let almanac = parse_input(&input).unwrap();
+        let chain = almanac.chain().unwrap();
+        for seed in almanac.seeds.iter() {
+            assert_eq!(chain.apply(*seed), almanac.maps[0].map.apply(*seed));
+        }
+        assert_eq!(chain.apply(DOMAIN.end - 10), 5);
+    }
+
+    #[test]
+    fn test_seed_ranges() {
+        let almanac = parse_input(INPUT).unwrap();
+        assert_eq!(
+            almanac.seed_ranges(),
+            Ok(vec![Range::from_len(79, 14), Range::from_len(55, 13)])
+        );
+        let almanac = parse_input("seeds: 1 2 3\n\na-to-b map:\n0 0 1").unwrap();
+        assert_eq!(almanac.seed_ranges(), Err(AlmanacError::OddSeedCount(3)));
+        for (start, len) in [(1, -5), (i64::MAX, 1)] {
+            let input = format!("seeds: {start} {len}\n\na-to-b map:\n0 0 1");
+            let almanac = parse_input(&input).unwrap();
+            assert_eq!(
+                almanac.seed_ranges(),
+                Err(AlmanacError::InvalidSeedRange { start, len })
+            );
+        }
+    }
+}
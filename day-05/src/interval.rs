@@ -0,0 +1,417 @@
+use std::fmt;
+
+// Half-open range `start..end`
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Range {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Range {
+    pub fn new(start: i64, end: i64) -> Self {
+        Range { start, end }
+    }
+
+    pub fn from_len(start: i64, len: i64) -> Self {
+        Range {
+            start,
+            end: start + len,
+        }
+    }
+
+    // `None` for a negative length or an end past `i64::MAX`
+    pub fn checked_from_len(start: i64, len: i64) -> Option<Self> {
+        if len < 0 {
+            return None;
+        }
+        Some(Range::new(start, start.checked_add(len)?))
+    }
+
+    pub fn len(&self) -> i64 {
+        (self.end - self.start).max(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    pub fn contains(&self, x: i64) -> bool {
+        self.start <= x && x < self.end
+    }
+
+    pub fn covers(&self, other: &Range) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    pub fn intersect(&self, other: &Range) -> Option<Range> {
+        let r = Range::new(self.start.max(other.start), self.end.min(other.end));
+        (!r.is_empty()).then_some(r)
+    }
+
+    pub fn shift(&self, offset: i64) -> Range {
+        Range::new(self.start + offset, self.end + offset)
+    }
+}
+
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+// Values in `src` are moved by `offset`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Segment {
+    pub src: Range,
+    pub offset: i64,
+}
+
+impl Segment {
+    // Same argument order as an almanac line: `<dst_start> <src_start> <len>`
+    pub fn from_almanac(dst_start: i64, src_start: i64, len: i64) -> Self {
+        Segment {
+            src: Range::from_len(src_start, len),
+            offset: dst_start - src_start,
+        }
+    }
+
+    // `None` when either side does not fit inside `DOMAIN`
+    pub fn checked_from_almanac(dst_start: i64, src_start: i64, len: i64) -> Option<Self> {
+        let src = Range::checked_from_len(src_start, len)?;
+        let dst = Range::checked_from_len(dst_start, len)?;
+        if !(DOMAIN.covers(&src) && DOMAIN.covers(&dst)) {
+            return None;
+        }
+        Some(Segment {
+            src,
+            offset: dst_start - src_start,
+        })
+    }
+
+    pub fn dst(&self) -> Range {
+        self.src.shift(self.offset)
+    }
+}
+
+// Bounds every mapping must fit in, so offsets of composed maps cannot overflow
+pub const DOMAIN: Range = Range {
+    start: i64::MIN / 4,
+    end: i64::MAX / 4,
+};
+
+// Used when a map has to be split over every value
+const EVERYTHING: Range = Range {
+    start: i64::MIN,
+    end: i64::MAX,
+};
+
+// Sorted, non-overlapping segments; values outside every segment map to themselves
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IntervalMap {
+    segments: Vec<Segment>,
+}
+
+impl IntervalMap {
+    pub fn new(mut segments: Vec<Segment>) -> Result<Self, String> {
+        segments.retain(|s| !s.src.is_empty());
+        segments.sort_by_key(|s| s.src.start);
+        for w in segments.windows(2) {
+            if w[0].src.end > w[1].src.start {
+                return Err(format!(
+                    "Overlapping mappings for {} and {}",
+                    w[0].src, w[1].src
+                ));
+            }
+        }
+        Ok(IntervalMap { segments })
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    fn find(&self, x: i64) -> Option<&Segment> {
+        let i = self.segments.partition_point(|s| s.src.end <= x);
+        self.segments.get(i).filter(|s| s.src.contains(x))
+    }
+
+    pub fn apply(&self, x: i64) -> i64 {
+        self.find(x).map_or(x, |s| x + s.offset)
+    }
+
+    // Pieces of the map covering `r`, with gaps between segments as identity pieces
+    pub fn split(&self, r: &Range) -> Vec<Segment> {
+        let mut out = Vec::new();
+        let mut cursor = r.start;
+        let first = self.segments.partition_point(|s| s.src.end <= r.start);
+        for s in self.segments[first..].iter() {
+            if s.src.start >= r.end {
+                break;
+            }
+            if cursor < s.src.start {
+                out.push(Segment {
+                    src: Range::new(cursor, s.src.start),
+                    offset: 0,
+                });
+            }
+            if let Some(inside) = r.intersect(&s.src) {
+                out.push(Segment {
+                    src: inside,
+                    offset: s.offset,
+                });
+            }
+            cursor = cursor.max(s.src.end);
+        }
+        if cursor < r.end {
+            out.push(Segment {
+                src: Range::new(cursor, r.end),
+                offset: 0,
+            });
+        }
+        out
+    }
+
+    pub fn apply_ranges(&self, ranges: &[Range]) -> Vec<Range> {
+        let out = ranges
+            .iter()
+            .filter(|r| !r.is_empty())
+            .flat_map(|r| self.split(r))
+            .map(|s| s.dst())
+            .collect();
+        merge_ranges(out)
+    }
+
+    // The map applying `self` first and `next` second
+    pub fn compose(&self, next: &IntervalMap) -> IntervalMap {
+        let mut pieces = Vec::new();
+        for p in self.split(&EVERYTHING) {
+            for q in next.split(&p.dst()) {
+                pieces.push(Segment {
+                    src: q.src.shift(-p.offset),
+                    offset: p.offset + q.offset,
+                });
+            }
+        }
+        IntervalMap::from_pieces(pieces)
+    }
+
+    // Drops identity pieces and joins neighbours with the same offset
+    fn from_pieces(mut pieces: Vec<Segment>) -> IntervalMap {
+        pieces.sort_by_key(|s| s.src.start);
+        let mut segments: Vec<Segment> = Vec::new();
+        for p in pieces.into_iter().filter(|p| p.offset != 0) {
+            match segments.last_mut() {
+                Some(last) if last.src.end == p.src.start && last.offset == p.offset => {
+                    last.src.end = p.src.end
+                }
+                _ => segments.push(p),
+            }
+        }
+        IntervalMap { segments }
+    }
+
+    // All values mapped into `target`
+    pub fn preimage(&self, target: &Range) -> Vec<Range> {
+        let out = self
+            .split(&EVERYTHING)
+            .iter()
+            .filter_map(|p| p.dst().intersect(target).map(|r| r.shift(-p.offset)))
+            .collect();
+        merge_ranges(out)
+    }
+}
+
+// Sorts the ranges and joins the ones that overlap or touch
+pub fn merge_ranges(mut ranges: Vec<Range>) -> Vec<Range> {
+    ranges.retain(|r| !r.is_empty());
+    ranges.sort();
+    let mut merged: Vec<Range> = Vec::with_capacity(ranges.len());
+    for r in ranges {
+        match merged.last_mut() {
+            Some(last) if r.start <= last.end => last.end = last.end.max(r.end),
+            _ => merged.push(r),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+    use test_utils::{for_all, Lcg};
+
+    fn random_map(rng: &mut Lcg) -> IntervalMap {
+        let mut segments = Vec::new();
+        let mut start = rng.range(0, 5);
+        for _ in 0..rng.below(5) {
+            let len = rng.range(1, 9);
+            segments.push(Segment::from_almanac(rng.range(0, 60), start, len));
+            start += len + rng.range(0, 4);
+        }
+        IntervalMap::new(segments).unwrap()
+    }
+
+    #[test]
+    fn test_apply() {
+        let map = IntervalMap::new(vec![
+            Segment::from_almanac(50, 98, 2),
+            Segment::from_almanac(52, 50, 48),
+        ])
+        .unwrap();
+        assert_eq!(map.apply(79), 81);
+        assert_eq!(map.apply(14), 14);
+        assert_eq!(map.apply(98), 50);
+        assert_eq!(map.apply(100), 100);
+        assert_eq!(
+            map.apply_ranges(&[Range::from_len(79, 14), Range::from_len(55, 13)]),
+            vec![Range::new(57, 70), Range::new(81, 95)]
+        );
+    }
+
+    #[test]
+    fn test_checked_from_almanac() {
+        assert_eq!(
+            Segment::checked_from_almanac(50, 98, 2),
+            Some(Segment::from_almanac(50, 98, 2))
+        );
+        assert_eq!(Segment::checked_from_almanac(0, 10, -5), None);
+        assert_eq!(Segment::checked_from_almanac(0, i64::MAX, 5), None);
+        assert_eq!(Segment::checked_from_almanac(i64::MAX, 0, 5), None);
+        assert_eq!(
+            Segment::checked_from_almanac(i64::MAX - 5, i64::MIN, 5),
+            None
+        );
+        assert_eq!(
+            Segment::checked_from_almanac(5, DOMAIN.end - 10, 10),
+            Some(Segment::from_almanac(5, DOMAIN.end - 10, 10))
+        );
+        assert_eq!(Segment::checked_from_almanac(5, DOMAIN.end - 10, 11), None);
+        assert_eq!(Segment::checked_from_almanac(DOMAIN.start - 1, 0, 1), None);
+        assert_eq!(Range::checked_from_len(1, -5), None);
+        assert_eq!(Range::checked_from_len(1, 0), Some(Range::new(1, 1)));
+    }
+
+    #[test]
+    fn test_overlap_rejected() {
+        let segments = vec![
+            Segment::from_almanac(0, 10, 5),
+            Segment::from_almanac(0, 14, 2),
+        ];
+        assert!(IntervalMap::new(segments).is_err());
+        let segments = vec![
+            Segment::from_almanac(0, 10, 5),
+            Segment::from_almanac(0, 15, 2),
+        ];
+        assert!(IntervalMap::new(segments).is_ok());
+    }
+
+    #[test]
+    fn test_compose() {
+        let a = IntervalMap::new(vec![Segment::from_almanac(20, 0, 10)]).unwrap();
+        let b = IntervalMap::new(vec![Segment::from_almanac(0, 25, 10)]).unwrap();
+        let c = a.compose(&b);
+        assert_eq!(c.apply(3), 23);
+        assert_eq!(c.apply(7), 2);
+        assert_eq!(c.apply(27), 2);
+        assert_eq!(c.apply(22), 22);
+        assert_eq!(
+            c.segments(),
+            &[
+                Segment::from_almanac(20, 0, 5),
+                Segment::from_almanac(0, 5, 5),
+                Segment::from_almanac(0, 25, 10),
+            ]
+        );
+        assert_eq!(a.compose(&IntervalMap::default()), a);
+
+        // Values outside `DOMAIN` pass through every map unchanged
+        let edge = IntervalMap::new(vec![Segment::from_almanac(5, DOMAIN.end - 10, 10)]).unwrap();
+        let c = IntervalMap::default().compose(&edge);
+        assert_eq!(c.apply(DOMAIN.end - 1), 14);
+        assert_eq!(c.apply(DOMAIN.end), DOMAIN.end);
+        assert_eq!(
+            c.preimage(&Range::from_len(i64::MAX - 1, 1)),
+            vec![Range::from_len(i64::MAX - 1, 1)]
+        );
+    }
+
+    #[test]
+    fn test_preimage() {
+        let map = IntervalMap::new(vec![Segment::from_almanac(0, 25, 10)]).unwrap();
+        assert_eq!(
+            map.preimage(&Range::from_len(3, 1)),
+            vec![Range::from_len(3, 1), Range::from_len(28, 1)]
+        );
+        assert_eq!(map.preimage(&Range::from_len(27, 1)), vec![]);
+    }
+
+    #[test]
+    fn test_compose_matches_pointwise() {
+        let maps = |rng: &mut Lcg| {
+            (0..rng.range(1, 5))
+                .map(|_| random_map(rng))
+                .collect::<Vec<_>>()
+        };
+        for_all(1225, 500, maps, |maps| {
+            let chain = maps
+                .iter()
+                .fold(IntervalMap::default(), |acc, m| acc.compose(m));
+            assert!(chain
+                .segments()
+                .windows(2)
+                .all(|w| w[0].src.end <= w[1].src.start));
+            for x in -5..80 {
+                let expected = maps.iter().fold(x, |acc, m| m.apply(acc));
+                assert_eq!(chain.apply(x), expected, "{x}");
+                let target = Range::from_len(expected, 1);
+                let pre = chain.preimage(&target);
+                assert!(pre.iter().any(|r| r.contains(x)));
+                for y in pre.iter().flat_map(|r| r.start..r.end).take(1000) {
+                    assert_eq!(chain.apply(y), expected);
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn test_merge_ranges() {
+        let ranges = vec![
+            Range::new(5, 8),
+            Range::new(0, 2),
+            Range::new(2, 3),
+            Range::new(7, 10),
+            Range::new(4, 4),
+        ];
+        assert_eq!(
+            merge_ranges(ranges),
+            vec![Range::new(0, 3), Range::new(5, 10)]
+        );
+    }
+
+    #[test]
+    fn test_apply_ranges_matches_pointwise() {
+        let case = |rng: &mut Lcg| {
+            let map = random_map(rng);
+            let ranges = (0..rng.below(4))
+                .map(|_| Range::from_len(rng.range(0, 50), rng.range(0, 15)))
+                .collect::<Vec<_>>();
+            (map, ranges)
+        };
+        for_all(2023, 500, case, |(map, ranges)| {
+            let expected = ranges
+                .iter()
+                .flat_map(|r| r.start..r.end)
+                .map(|x| map.apply(x))
+                .collect::<BTreeSet<_>>();
+            let result = map.apply_ranges(ranges);
+            let actual = result
+                .iter()
+                .flat_map(|r| r.start..r.end)
+                .collect::<BTreeSet<_>>();
+            assert_eq!(actual, expected);
+            // Merged output is sorted and never touches itself
+            assert!(result.windows(2).all(|w| w[0].end < w[1].start));
+        });
+    }
+}
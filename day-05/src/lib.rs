@@ -0,0 +1,2 @@
+pub mod almanac;
+pub mod interval;
@@ -0,0 +1,30 @@
+use day_06::{
+    boat::{builtin_models, explore},
+    sheet::parse_sheet,
+};
+use std::fs;
+
+fn read_input() -> String {
+    let input: String = fs::read_to_string("src/bin/input.txt").expect("Expected input.txt");
+    input
+}
+
+fn main() {
+    let input: String = read_input();
+    let sheet = parse_sheet(input.as_str()).expect("Expected a valid race sheet");
+    for model in builtin_models() {
+        for race in sheet.races.iter() {
+            let result = explore(model.as_ref(), race);
+            println!(
+                "{}\ttime {}\trecord {}\tways {}\tbest hold {}\tmargin {}\twinning {:?}",
+                model.name(),
+                race.time,
+                race.record,
+                result.ways_to_win,
+                result.best_hold,
+                result.margin,
+                result.winning
+            )
+        }
+    }
+}
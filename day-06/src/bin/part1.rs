@@ -1,13 +1,4 @@
-use itertools::Itertools;
-use nom::{
-    bytes::complete::{is_not, tag},
-    character::complete::{digit1, space1},
-    combinator::map_res,
-    multi::{separated_list1},
-    sequence::{tuple},
-    IResult,
-};
-
+use day_06::{race::ways_to_win, sheet::parse_sheet};
 use std::fs;
 
 fn read_input() -> String {
@@ -15,42 +6,13 @@ fn read_input() -> String {
     input
 }
 
-fn parse_numbers(input: &str) -> IResult<&str, u32> {
-    map_res(digit1, |s: &str| s.parse::<u32>())(input)
-}
-
-fn parse_list(input: &str) -> IResult<&str, Vec<u32>> {
-    separated_list1(space1, parse_numbers)(input)
-}
-
-fn parse_line(input: &str) -> IResult<&str, (&str, &str, &str, Vec<u32>)> {
-    tuple((is_not(":"), tag(":"), space1, parse_list))(input)
-}
-
-fn parse_input(input: &str) -> Vec<Vec<u32>> {
-    return input
-        .lines()
-        .map(|x| parse_line(x).unwrap().1 .3)
-        .collect_vec();
-}
-
-fn run(input: String) -> i32 {
-    let matrix = parse_input(input.as_str());
-    let time = &matrix[0];
-    let dist = &matrix[1];
-
-    let mut race_mult = 1;
-    for (i, j) in time.iter().zip(dist.iter()) {
-        let mut races = 0;
-        for k in 0..*i {
-            let reach = k * (i - k);
-            if reach > *j {
-                races += 1;
-            }
-        }
-        race_mult *= races;
-    }
-    race_mult
+fn run(input: String) -> u128 {
+    let sheet = parse_sheet(input.as_str()).expect("Expected a valid race sheet");
+    sheet
+        .races
+        .iter()
+        .map(|r| ways_to_win(r.time, r.record))
+        .product()
 }
 
 fn main() {
@@ -60,7 +22,6 @@ fn main() {
 }
 
 #[cfg(test)]
-
 mod tests {
     use super::*;
     #[test]
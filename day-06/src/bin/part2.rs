@@ -1,5 +1,4 @@
-use itertools::Itertools;
-
+use day_06::{race::ways_to_win, sheet::parse_sheet};
 use std::fs;
 
 fn read_input() -> String {
@@ -7,41 +6,10 @@ fn read_input() -> String {
     input
 }
 
-fn run(input: String) -> u64 {
-    // let mut parse_time = pair(tag("Time:"), separated_list0(tag(" "), u32));
-    // let mut parse_dist = pair(tag("Distance:"), separated_list0(tag(" "), u32));
-    let mut li = input.lines();
-    let time: u64 = li
-        .next()
-        .unwrap()
-        .split(':')
-        .last()
-        .unwrap()
-        .split(' ')
-        .filter(|x| !x.is_empty())
-        .join("")
-        .parse()
-        .unwrap();
-    let dist: u64 = li
-        .next()
-        .unwrap()
-        .split(':')
-        .last()
-        .unwrap()
-        .split(' ')
-        .filter(|x| !x.is_empty())
-        .join("")
-        .parse()
-        .unwrap();
-
-    let mut races = 0;
-    for k in 0..time {
-        let reach = k * (time - k);
-        if reach > dist {
-            races += 1;
-        }
-    }
-    races
+fn run(input: String) -> u128 {
+    let sheet = parse_sheet(input.as_str()).expect("Expected a valid race sheet");
+    let race = sheet.kerned.expect("Expected the joined columns to fit");
+    ways_to_win(race.time, race.record)
 }
 
 fn main() {
@@ -51,7 +19,6 @@ fn main() {
 }
 
 #[cfg(test)]
-
 mod tests {
     use super::*;
     #[test]
@@ -0,0 +1,242 @@
+use crate::{race::ways_to_win, sheet::Race};
+use num::integer::Roots;
+use std::ops::RangeInclusive;
+
+pub trait BoatModel {
+    fn name(&self) -> String;
+    fn speed(&self, hold: u128) -> u128;
+
+    fn distance(&self, hold: u128, time: u128) -> u128 {
+        self.speed(hold).saturating_mul(time.saturating_sub(hold))
+    }
+
+    // An exact answer without trying every hold time, for models which have one
+    fn closed_form(&self, _race: &Race) -> Option<Exploration> {
+        None
+    }
+}
+
+// Every millisecond of holding adds `accel` mm/ms; `accel: 1` is the puzzle rule
+pub struct Linear {
+    pub accel: u128,
+}
+
+impl BoatModel for Linear {
+    fn name(&self) -> String {
+        format!("linear({})", self.accel)
+    }
+
+    fn speed(&self, hold: u128) -> u128 {
+        self.accel.saturating_mul(hold)
+    }
+
+    // accel * k * (time - k) > record exactly when k * (time - k) > record / accel, rounded down
+    fn closed_form(&self, race: &Race) -> Option<Exploration> {
+        let (ways, best_hold) = match self.accel {
+            0 => (0, 0),
+            accel => (ways_to_win(race.time, race.record / accel), race.time / 2),
+        };
+        let best_distance = self.distance(best_hold, race.time);
+        Some(Exploration {
+            winning: match ways {
+                0 => Vec::new(),
+                // The winning holds are symmetric around the best one, and `ways < time`
+                _ => {
+                    let first = (race.time - ways) / 2 + 1;
+                    vec![first..=first + ways - 1]
+                }
+            },
+            ways_to_win: ways,
+            best_hold,
+            best_distance,
+            margin: best_distance.saturating_sub(race.record),
+        })
+    }
+}
+
+// Speed grows with the square root of the hold time
+pub struct DiminishingReturns {
+    pub factor: u128,
+}
+
+impl BoatModel for DiminishingReturns {
+    fn name(&self) -> String {
+        format!("diminishing({})", self.factor)
+    }
+
+    fn speed(&self, hold: u128) -> u128 {
+        self.factor.saturating_mul(hold).sqrt()
+    }
+}
+
+// Linear acceleration up to `max_speed`
+pub struct TopSpeed {
+    pub accel: u128,
+    pub max_speed: u128,
+}
+
+impl BoatModel for TopSpeed {
+    fn name(&self) -> String {
+        format!("top-speed({}, {})", self.accel, self.max_speed)
+    }
+
+    fn speed(&self, hold: u128) -> u128 {
+        self.accel.saturating_mul(hold).min(self.max_speed)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Exploration {
+    pub winning: Vec<RangeInclusive<u128>>,
+    pub ways_to_win: u128,
+    pub best_hold: u128,
+    pub best_distance: u128,
+    // How far the best hold beats the record, 0 when the race cannot be won
+    pub margin: u128,
+}
+
+// Uses the model's closed form when it has one, otherwise tries every hold time, which costs
+// time linear in the race time and is only practical for the separate races, not the kerned one
+pub fn explore(model: &dyn BoatModel, race: &Race) -> Exploration {
+    model
+        .closed_form(race)
+        .unwrap_or_else(|| explore_every_hold(model, race))
+}
+
+fn explore_every_hold(model: &dyn BoatModel, race: &Race) -> Exploration {
+    let mut winning: Vec<RangeInclusive<u128>> = Vec::new();
+    let mut best_hold = 0;
+    let mut best_distance = 0;
+    for hold in 0..=race.time {
+        let distance = model.distance(hold, race.time);
+        if distance > best_distance {
+            best_hold = hold;
+            best_distance = distance;
+        }
+        if distance > race.record {
+            match winning.last_mut() {
+                Some(last) if *last.end() + 1 == hold => *last = *last.start()..=hold,
+                _ => winning.push(hold..=hold),
+            }
+        }
+    }
+    Exploration {
+        ways_to_win: winning.iter().map(|r| r.end() - r.start() + 1).sum(),
+        winning,
+        best_hold,
+        best_distance,
+        margin: best_distance.saturating_sub(race.record),
+    }
+}
+
+pub fn builtin_models() -> Vec<Box<dyn BoatModel>> {
+    vec![
+        Box::new(Linear { accel: 1 }),
+        Box::new(DiminishingReturns { factor: 16 }),
+        Box::new(TopSpeed {
+            accel: 1,
+            max_speed: 10,
+        }),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_linear_matches_solver() {
+        for (time, record) in [(7, 9), (15, 40), (30, 200), (40, 0), (9, 100)] {
+            let race = Race { time, record };
+            let result = explore(&Linear { accel: 1 }, &race);
+            assert_eq!(result.ways_to_win, ways_to_win(time, record));
+            assert!(result.winning.len() <= 1);
+        }
+        let result = explore(&Linear { accel: 1 }, &Race { time: 7, record: 9 });
+        assert_eq!(result.winning, vec![2..=5]);
+        assert_eq!(result.best_hold, 3);
+        assert_eq!(result.best_distance, 12);
+        assert_eq!(result.margin, 3);
+    }
+
+    #[test]
+    fn test_linear_closed_form() {
+        for accel in 0..4 {
+            let model = Linear { accel };
+            for time in 0..30 {
+                for record in 0..(accel * time * time / 4 + 3) {
+                    let race = Race { time, record };
+                    assert_eq!(
+                        model.closed_form(&race).unwrap(),
+                        explore_every_hold(&model, &race),
+                        "accel {accel}, time {time}, record {record}"
+                    );
+                }
+            }
+        }
+        // A kerned part 2 race, far too long to try every hold
+        let race = Race {
+            time: 71530 * 1_000_000_000,
+            record: 940200,
+        };
+        let result = explore(&Linear { accel: 1 }, &race);
+        assert_eq!(result.ways_to_win, ways_to_win(race.time, race.record));
+        // The longest race a sheet can hold
+        let result = explore(
+            &Linear { accel: 1 },
+            &Race {
+                time: u128::MAX,
+                record: 0,
+            },
+        );
+        assert_eq!(result.ways_to_win, u128::MAX - 1);
+        assert_eq!(result.winning, vec![1..=u128::MAX - 1]);
+        for (accel, record) in [(1, u128::MAX), (2, u128::MAX / 2), (u128::MAX, 5)] {
+            let race = Race {
+                time: u128::MAX,
+                record,
+            };
+            let result = explore(&Linear { accel }, &race);
+            let ways = ways_to_win(race.time, race.record / accel);
+            assert_eq!(result.ways_to_win, ways);
+            let first = *result.winning[0].start();
+            assert_eq!(result.winning, vec![first..=u128::MAX - first]);
+        }
+    }
+
+    #[test]
+    fn test_top_speed() {
+        let model = TopSpeed {
+            accel: 2,
+            max_speed: 6,
+        };
+        let result = explore(
+            &model,
+            &Race {
+                time: 10,
+                record: 30,
+            },
+        );
+        // Speed caps at a hold of 3, after that holding only loses time
+        assert_eq!(result.best_hold, 3);
+        assert_eq!(result.best_distance, 42);
+        assert_eq!(result.winning, vec![2..=4]);
+    }
+
+    #[test]
+    fn test_diminishing_returns() {
+        let model = DiminishingReturns { factor: 4 };
+        assert_eq!(model.speed(9), 6);
+        let result = explore(
+            &model,
+            &Race {
+                time: 9,
+                record: 100,
+            },
+        );
+        assert_eq!(result.winning, vec![]);
+        assert_eq!(result.margin, 0);
+        assert_eq!(result.best_hold, 4);
+        assert_eq!(result.best_distance, 20);
+    }
+}
@@ -0,0 +1,3 @@
+pub mod boat;
+pub mod race;
+pub mod sheet;
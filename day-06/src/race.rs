@@ -0,0 +1,78 @@
+use num::{BigUint, One, ToPrimitive, Zero};
+
+fn distance(hold: &BigUint, time: &BigUint) -> BigUint {
+    hold * (time - hold)
+}
+
+// Number of hold times `k` in `0..=time` with `k * (time - k) > record`
+pub fn ways_to_win_big(time: &BigUint, record: &BigUint) -> BigUint {
+    // The best hold is half the race time; if that loses, everything does
+    if distance(&(time / 2_u32), time) <= *record {
+        return BigUint::zero();
+    }
+    let square = time * time;
+    let bound = record * 4_u32;
+    // Smaller root of k^2 - time * k + record = 0, then nudged onto the first winning k
+    let root = (&square - &bound).sqrt();
+    let mut lo = if *time > root {
+        (time - &root) / 2_u32
+    } else {
+        BigUint::zero()
+    };
+    while distance(&lo, time) <= *record {
+        lo += 1_u32;
+    }
+    while !lo.is_zero() && distance(&(&lo - 1_u32), time) > *record {
+        lo -= 1_u32;
+    }
+    time - &lo - lo + BigUint::one()
+}
+
+pub fn ways_to_win(time: u128, record: u128) -> u128 {
+    ways_to_win_big(&BigUint::from(time), &BigUint::from(record))
+        .to_u128()
+        .expect("Expected at most time - 1 ways to win")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ways_to_win_brute(time: u128, record: u128) -> u128 {
+        (0..=time).filter(|k| k * (time - k) > record).count() as u128
+    }
+
+    #[test]
+    fn test_examples() {
+        assert_eq!(ways_to_win(7, 9), 4);
+        assert_eq!(ways_to_win(15, 40), 8);
+        assert_eq!(ways_to_win(30, 200), 9);
+        assert_eq!(ways_to_win(71530, 940200), 71503);
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        for time in 0..60 {
+            for record in 0..(time * time / 4 + 3) {
+                assert_eq!(
+                    ways_to_win(time, record),
+                    ways_to_win_brute(time, record),
+                    "time {time}, record {record}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_large_values() {
+        // k * (time - k) > 0 for every k except the two ends
+        assert_eq!(ways_to_win(u128::MAX, 0), u128::MAX - 1);
+        let time = 1_u128 << 64;
+        let half = time / 2;
+        assert_eq!(ways_to_win(time, half * half - 1), 1);
+        assert_eq!(ways_to_win(time, half * half), 0);
+        assert_eq!(ways_to_win(time, (half - 1) * (half + 1) - 1), 3);
+        let big = BigUint::from(10_u32).pow(60);
+        assert_eq!(ways_to_win_big(&big, &BigUint::zero()), &big - 1_u32);
+    }
+}
@@ -0,0 +1,176 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::{digit1, multispace0, space1},
+    combinator::all_consuming,
+    multi::separated_list1,
+    sequence::{delimited, pair, preceded},
+    IResult,
+};
+use std::fmt;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Race {
+    pub time: u128,
+    pub record: u128,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SheetError {
+    Syntax(String),
+    ColumnMismatch { times: usize, distances: usize },
+    Overflow(String),
+}
+
+impl fmt::Display for SheetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SheetError::Syntax(e) => write!(f, "Invalid race sheet: {e}"),
+            SheetError::ColumnMismatch { times, distances } => {
+                write!(f, "Race sheet has {times} times but {distances} distances")
+            }
+            SheetError::Overflow(x) => write!(f, "Number {x} does not fit in u128"),
+        }
+    }
+}
+
+impl std::error::Error for SheetError {}
+
+// Both readings of the sheet: one race per column, or one race with the columns joined.
+// Joining can overflow even when every column fits, so that reading may be an error on its own.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RaceSheet {
+    pub races: Vec<Race>,
+    pub kerned: Result<Race, SheetError>,
+}
+
+fn parse_row<'a>(name: &'static str) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<&'a str>> {
+    delimited(
+        multispace0,
+        preceded(pair(tag(name), space1), separated_list1(space1, digit1)),
+        multispace0,
+    )
+}
+
+fn parse_number(digits: &str) -> Result<u128, SheetError> {
+    digits
+        .parse()
+        .map_err(|_| SheetError::Overflow(digits.to_string()))
+}
+
+pub fn parse_sheet(input: &str) -> Result<RaceSheet, SheetError> {
+    let (_, (times, distances)) =
+        all_consuming(pair(parse_row("Time:"), parse_row("Distance:")))(input)
+            .map_err(|e| SheetError::Syntax(e.to_string()))?;
+    if times.len() != distances.len() {
+        return Err(SheetError::ColumnMismatch {
+            times: times.len(),
+            distances: distances.len(),
+        });
+    }
+    let races = times
+        .iter()
+        .zip(distances.iter())
+        .map(|(t, d)| {
+            Ok(Race {
+                time: parse_number(t)?,
+                record: parse_number(d)?,
+            })
+        })
+        .collect::<Result<Vec<_>, SheetError>>()?;
+    let kerned = parse_number(&times.concat()).and_then(|time| {
+        Ok(Race {
+            time,
+            record: parse_number(&distances.concat())?,
+        })
+    });
+    Ok(RaceSheet { races, kerned })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sheet() {
+        let sheet = parse_sheet(
+            "Time:      7  15   30
+        Distance:  9  40  200",
+        )
+        .unwrap();
+        assert_eq!(sheet.races.len(), 3);
+        assert_eq!(
+            sheet.races[1],
+            Race {
+                time: 15,
+                record: 40
+            }
+        );
+        assert_eq!(
+            sheet.kerned,
+            Ok(Race {
+                time: 71530,
+                record: 940200
+            })
+        );
+    }
+
+    #[test]
+    fn test_kerning_keeps_digits() {
+        let sheet = parse_sheet("Time: 1 05\nDistance: 0 7\n").unwrap();
+        assert_eq!(sheet.races[1], Race { time: 5, record: 7 });
+        assert_eq!(
+            sheet.kerned,
+            Ok(Race {
+                time: 105,
+                record: 7
+            })
+        );
+    }
+
+    #[test]
+    fn test_invalid_sheets() {
+        assert_eq!(
+            parse_sheet("Time: 7 15\nDistance: 9"),
+            Err(SheetError::ColumnMismatch {
+                times: 2,
+                distances: 1
+            })
+        );
+        assert!(matches!(
+            parse_sheet("Time: 7\nDistance: x"),
+            Err(SheetError::Syntax(_))
+        ));
+        assert!(matches!(
+            parse_sheet("Distance: 7\nTime: 9"),
+            Err(SheetError::Syntax(_))
+        ));
+        assert_eq!(
+            parse_sheet("Time: 340282366920938463463374607431768211456\nDistance: 1"),
+            Err(SheetError::Overflow(
+                "340282366920938463463374607431768211456".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_kerned_overflow() {
+        let sheet =
+            parse_sheet("Time: 340282366920938463463374607431768211455 1\nDistance: 1 1").unwrap();
+        assert_eq!(
+            sheet.races,
+            vec![
+                Race {
+                    time: u128::MAX,
+                    record: 1
+                },
+                Race { time: 1, record: 1 }
+            ]
+        );
+        assert_eq!(
+            sheet.kerned,
+            Err(SheetError::Overflow(
+                "3402823669209384634633746074317682114551".to_string()
+            ))
+        );
+    }
+}
@@ -1,122 +1,29 @@
-use itertools::{Itertools};
-use nom::{
-    bytes::complete::{is_not},
-    character::complete::{digit1, space1},
-    combinator::map_res,
-    multi::{separated_list1},
-    sequence::{tuple},
-    IResult,
+use day_07::{
+    hand::{parse_input, total_winnings},
+    rules::Ruleset,
 };
-
-use std::{fs, vec};
+use std::fs;
 
 fn read_input() -> String {
     let input: String = fs::read_to_string("src/bin/input.txt").expect("Expected input.txt");
     input
 }
 
-#[derive(Debug)]
-struct Hand {
-    // Original chars A, K, Q, J, T, 9, 8, 7, 6, 5, 4, 3, or 2
-    hand: Vec<char>,
-    bid: u32,
-}
-
-impl Hand {
-    fn get_group(&self) -> u8 {
-        let mut freq = vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
-        for c in self.hand.clone() {
-            for (i, b) in vec![
-                'A', 'K', 'Q', 'J', 'T', '9', '8', '7', '6', '5', '4', '3', '2',
-            ]
-            .into_iter()
-            .enumerate()
-            {
-                if c == b {
-                    freq[i] += 1;
-                }
-            }
-        }
-        let freq_s = freq.into_iter().sorted().rev().collect_vec();
-        match freq_s[0] {
-            5 => 10,
-            4 => 9,
-            3 => match freq_s[1] {
-                2 => 8,
-                _ => 7,
-            },
-            2 => match freq_s[1] {
-                2 => 6,
-                _ => 5,
-            },
-            _ => 4,
-        }
-    }
-
-    fn get_pos(&self, i: usize) -> u8 {
-        match self.hand[i] {
-            'A' => 14,
-            'K' => 13,
-            'Q' => 12,
-            'J' => 11,
-            'T' => 10,
-            _ => String::from(self.hand[i]).parse().unwrap(),
-        }
-    }
-}
-
-fn parse_numbers(input: &str) -> IResult<&str, u32> {
-    map_res(digit1, |s: &str| s.parse::<u32>())(input)
-}
-
-fn parse_list(input: &str) -> IResult<&str, Vec<u32>> {
-    separated_list1(space1, parse_numbers)(input)
-}
-
-fn parse_line(input: &str) -> (&str, &str, u32) {
-    tuple((is_not(" "), space1, parse_numbers))(input)
-        .expect("hand - bid pair expected")
-        .1
-}
-
-fn parse_input(input: &str) -> Vec<Hand> {
-    return input
-        .lines()
-        .map(parse_line)
-        .map(|(hand, _x, bid)| Hand {
-            hand: hand.chars().collect_vec(),
-            bid,
-        })
-        .collect_vec();
+fn run(input: String) -> Result<u32, String> {
+    Ok(total_winnings(parse_input(
+        input.as_str(),
+        &Ruleset::standard(),
+    )?))
 }
 
-fn run(input: String) -> u32 {
-    let hand = parse_input(input.as_str());
-
-    let hand_s: Vec<Hand> = hand
-        .into_iter()
-        .sorted_by(|a, b| a.get_pos(4).cmp(&b.get_pos(4)))
-        .sorted_by(|a, b| a.get_pos(3).cmp(&b.get_pos(3)))
-        .sorted_by(|a, b| a.get_pos(2).cmp(&b.get_pos(2)))
-        .sorted_by(|a, b| a.get_pos(1).cmp(&b.get_pos(1)))
-        .sorted_by(|a, b| a.get_pos(0).cmp(&b.get_pos(0)))
-        .sorted_by(|a, b| a.get_group().cmp(&b.get_group()))
-        .collect_vec();
-    hand_s
-        .into_iter()
-        .enumerate()
-        .map(|(rank, hand)| (1 + rank as u32) * hand.bid)
-        .sum()
-}
-
-fn main() {
+fn main() -> Result<(), String> {
     let input: String = read_input();
-    let sum = run(input);
-    println!("{sum}")
+    let sum = run(input)?;
+    println!("{sum}");
+    Ok(())
 }
 
 #[cfg(test)]
-
 mod tests {
     use super::*;
     #[test]
@@ -126,6 +33,6 @@ T55J5 684
 KK677 28
 KTJJT 220
 QQQJA 483";
-        assert_eq!(run(input.to_string()), 6440);
+        assert_eq!(run(input.to_string()), Ok(6440));
     }
 }
@@ -0,0 +1,19 @@
+use day_07::{hand::parse_input, report::report, rules::Ruleset};
+use std::{env, fs};
+
+fn read_input() -> String {
+    let input: String = fs::read_to_string("src/bin/input.txt").expect("Expected input.txt");
+    input
+}
+
+fn main() -> Result<(), String> {
+    let input: String = read_input();
+    // `report jokers` uses the part 2 rules
+    let rules = match env::args().nth(1).as_deref() {
+        Some("jokers") => Ruleset::jokers(),
+        _ => Ruleset::standard(),
+    };
+    let hands = parse_input(input.as_str(), &rules)?;
+    println!("{}", report(hands, &rules));
+    Ok(())
+}
@@ -0,0 +1,238 @@
+use crate::rules::{HandType, Ruleset};
+use itertools::Itertools;
+use nom::{
+    bytes::complete::is_not,
+    character::complete::{space1, u32},
+    combinator::all_consuming,
+    sequence::tuple,
+    IResult,
+};
+use std::cmp::Ordering;
+
+// The ruleset's tier for the hand type, the hand type, then the card strengths from first to last
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HandRank {
+    pub tier: usize,
+    pub hand_type: HandType,
+    pub positions: Vec<usize>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Hand {
+    pub hand: Vec<char>,
+    pub bid: u32,
+    rank: HandRank,
+}
+
+impl Hand {
+    pub fn new(hand: &str, bid: u32, rules: &Ruleset) -> Result<Self, String> {
+        let hand = hand.chars().collect_vec();
+        let group = rules.get_group(&hand);
+        let positions = if rules.tie_break {
+            hand.iter().map(|c| rules.get_pos(*c)).try_collect()?
+        } else {
+            for c in hand.iter() {
+                rules.get_pos(*c)?;
+            }
+            Vec::new()
+        };
+        Ok(Hand {
+            hand,
+            bid,
+            rank: HandRank {
+                tier: rules.get_tier(&group),
+                hand_type: group,
+                positions,
+            },
+        })
+    }
+
+    pub fn get_group(&self) -> &HandType {
+        &self.rank.hand_type
+    }
+
+    pub fn rank(&self) -> &HandRank {
+        &self.rank
+    }
+}
+
+// Hands compare by strength only, so two hands with the same cards are equal whatever their bid
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.rank.cmp(&other.rank)
+    }
+}
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Hand {
+    fn eq(&self, other: &Self) -> bool {
+        self.rank == other.rank
+    }
+}
+
+impl Eq for Hand {}
+
+fn parse_line(input: &str) -> IResult<&str, (&str, &str, u32)> {
+    tuple((is_not(" "), space1, u32))(input)
+}
+
+pub fn parse_input(input: &str, rules: &Ruleset) -> Result<Vec<Hand>, String> {
+    input
+        .lines()
+        .map(|l| {
+            let (_, (hand, _x, bid)) = all_consuming(parse_line)(l.trim())
+                .map_err(|_| format!("Expected a hand and a bid in '{l}'"))?;
+            Hand::new(hand, bid, rules)
+        })
+        .collect()
+}
+
+pub fn total_winnings(mut hands: Vec<Hand>) -> u32 {
+    hands.sort();
+    hands
+        .into_iter()
+        .enumerate()
+        .map(|(rank, hand)| (1 + rank as u32) * hand.bid)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+
+    // The previous ranking: a stable sort per card from last to first, then by hand type
+    fn legacy_order(hands: Vec<Hand>, rules: &Ruleset) -> Vec<Hand> {
+        let pos = |h: &Hand, i: usize| rules.get_pos(h.hand[i]).unwrap();
+        hands
+            .into_iter()
+            .sorted_by(|a, b| pos(a, 4).cmp(&pos(b, 4)))
+            .sorted_by(|a, b| pos(a, 3).cmp(&pos(b, 3)))
+            .sorted_by(|a, b| pos(a, 2).cmp(&pos(b, 2)))
+            .sorted_by(|a, b| pos(a, 1).cmp(&pos(b, 1)))
+            .sorted_by(|a, b| pos(a, 0).cmp(&pos(b, 0)))
+            .sorted_by(|a, b| a.get_group().cmp(b.get_group()))
+            .collect_vec()
+    }
+
+    fn all_hands(rules: &Ruleset) -> Vec<Hand> {
+        // Every hand built from a reduced alphabet, which still covers every hand type
+        let alphabet = ['A', 'J', 'T', '9', '2'];
+        (0..5)
+            .map(|_| alphabet.iter())
+            .multi_cartesian_product()
+            .enumerate()
+            .map(|(i, cards)| Hand::new(&String::from_iter(cards), i as u32, rules).unwrap())
+            .collect_vec()
+    }
+
+    #[test]
+    fn test_winnings() {
+        assert_eq!(
+            total_winnings(parse_input(INPUT, &Ruleset::standard()).unwrap()),
+            6440
+        );
+        assert_eq!(
+            total_winnings(parse_input(INPUT, &Ruleset::jokers()).unwrap()),
+            5905
+        );
+        assert_eq!(
+            parse_input("32T3K 765\n32X3K 1", &Ruleset::standard()).map(|_| ()),
+            Err("Unknown card 'X'".to_string())
+        );
+        assert!(parse_input("32T3K", &Ruleset::standard()).is_err());
+        assert!(parse_input("32T3K 7x", &Ruleset::standard()).is_err());
+    }
+
+    #[test]
+    fn test_matches_legacy_order() {
+        for rules in [Ruleset::standard(), Ruleset::jokers()] {
+            let hands = all_hands(&rules);
+            let legacy = legacy_order(hands.clone(), &rules)
+                .into_iter()
+                .map(|h| h.hand)
+                .collect_vec();
+            let mut sorted = hands;
+            sorted.sort();
+            let sorted = sorted.into_iter().map(|h| h.hand).collect_vec();
+            assert_eq!(sorted, legacy);
+        }
+    }
+
+    #[test]
+    fn test_rank() {
+        let standard = Ruleset::standard();
+        let jokers = Ruleset::jokers();
+        let hand = |x: &str, r: &Ruleset| Hand::new(x, 0, r).unwrap();
+        assert!(hand("JJJJJ", &standard) > hand("AAAA2", &standard));
+        assert!(hand("JJJJJ", &jokers) < hand("22222", &jokers));
+        assert!(hand("QJJQ2", &jokers) > hand("QQQ22", &jokers));
+        assert_eq!(
+            Hand::new("KK677", 1, &standard).unwrap(),
+            Hand::new("KK677", 2, &standard).unwrap()
+        );
+        assert!(Hand::new("KK6X7", 1, &standard).is_err());
+    }
+
+    #[test]
+    fn test_variable_length() {
+        let rules = Ruleset {
+            order: "abcdefghijklmnopqrstuvwxyz".chars().collect(),
+            ..Ruleset::standard()
+        };
+        let hand = |x: &str| Hand::new(x, 0, &rules).unwrap();
+        assert!(hand("aaabbbc") > hand("zzzyyxw"));
+        assert!(hand("aaaab") > hand("zzzyy"));
+        assert!(hand("aab") < hand("bba"));
+        assert!(hand("zz") > hand("zy"));
+        assert_eq!(hand("qwertyuiopasdfghjkl").get_group().0, vec![1; 19]);
+        let input = "aaa 1\nab 10\nbbbbbbbbbbbbbbbbbbbbc 100";
+        assert_eq!(
+            total_winnings(parse_input(input, &rules).unwrap()),
+            10 + 2 + 300
+        );
+        // Past 255 cards in the order, positions must not wrap
+        let rules = Ruleset {
+            order: (0..300)
+                .map(|i| char::from_u32(0x100 + i).unwrap())
+                .collect(),
+            ..Ruleset::standard()
+        };
+        let card = |i: usize| rules.order[i].to_string();
+        let hand = |x: &str| Hand::new(x, 0, &rules).unwrap();
+        assert!(hand(&card(256)) > hand(&card(0)));
+    }
+
+    #[test]
+    fn test_variants() {
+        let no_tie_break = Ruleset {
+            tie_break: false,
+            ..Ruleset::standard()
+        };
+        let hand = |x: &str, r: &Ruleset| Hand::new(x, 0, r).unwrap();
+        assert_eq!(hand("KK677", &no_tie_break), hand("22334", &no_tie_break));
+        let pairs_first = Ruleset {
+            hand_types: vec![(vec![2, 2], 2), (vec![3], 1)],
+            ..Ruleset::standard()
+        };
+        assert!(hand("22334", &pairs_first) > hand("AAAKQ", &pairs_first));
+        assert!(hand("22234", &pairs_first) > hand("22224", &pairs_first));
+        assert!(hand("22233", &pairs_first) > hand("22234", &pairs_first));
+        let two_jokers = Ruleset {
+            wildcards: vec!['J', 'Q'],
+            ..Ruleset::jokers()
+        };
+        assert_eq!(hand("QJ234", &two_jokers).get_group().0, vec![3, 1, 1]);
+        assert!(hand("Q2345", &two_jokers) < hand("2Q345", &two_jokers));
+    }
+}
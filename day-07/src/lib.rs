@@ -0,0 +1,3 @@
+pub mod hand;
+pub mod report;
+pub mod rules;
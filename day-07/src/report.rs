@@ -0,0 +1,126 @@
+use crate::{
+    hand::Hand,
+    rules::{HandType, Ruleset},
+};
+use std::{collections::BTreeMap, fmt};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HandReport {
+    pub hand: String,
+    pub bid: u32,
+    pub group: HandType,
+    // Hand type if no card were wild
+    pub base_group: HandType,
+    pub rank: u32,
+    pub winnings: u32,
+}
+
+impl HandReport {
+    pub fn improved(&self) -> bool {
+        self.group != self.base_group
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Report {
+    pub hands: Vec<HandReport>,
+    pub histogram: BTreeMap<HandType, usize>,
+    pub total: u32,
+}
+
+pub fn report(mut hands: Vec<Hand>, rules: &Ruleset) -> Report {
+    let plain = Ruleset {
+        wildcards: Vec::new(),
+        ..rules.clone()
+    };
+    hands.sort();
+    let hands = hands
+        .iter()
+        .enumerate()
+        .map(|(i, h)| HandReport {
+            hand: String::from_iter(&h.hand),
+            bid: h.bid,
+            group: h.get_group().clone(),
+            base_group: plain.get_group(&h.hand),
+            rank: i as u32 + 1,
+            winnings: (i as u32 + 1) * h.bid,
+        })
+        .collect::<Vec<_>>();
+    let mut histogram = BTreeMap::new();
+    for h in hands.iter() {
+        *histogram.entry(h.group.clone()).or_insert(0) += 1;
+    }
+    Report {
+        total: hands.iter().map(|h| h.winnings).sum(),
+        hands,
+        histogram,
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for h in self.hands.iter() {
+            write!(
+                f,
+                "{}\t{}\trank {}\tbid {}\twinnings {}",
+                h.hand, h.group, h.rank, h.bid, h.winnings
+            )?;
+            if h.improved() {
+                write!(f, "\t{} -> {}", h.base_group, h.group)?;
+            }
+            writeln!(f)?;
+        }
+        for (group, count) in self.histogram.iter().rev() {
+            writeln!(f, "{}\t{}", group, count)?;
+        }
+        write!(f, "total\t{}", self.total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hand::parse_input;
+
+    const INPUT: &str = "32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+
+    #[test]
+    fn test_report() {
+        let rules = Ruleset::standard();
+        let report = report(parse_input(INPUT, &rules).unwrap(), &rules);
+        assert_eq!(report.total, 6440);
+        assert_eq!(report.hands[0].hand, "32T3K");
+        assert_eq!(report.hands[4].hand, "QQQJA");
+        assert_eq!(report.hands[4].winnings, 5 * 483);
+        assert_eq!(
+            report.histogram,
+            BTreeMap::from([
+                (HandType(vec![2, 1, 1, 1]), 1),
+                (HandType(vec![2, 2, 1]), 2),
+                (HandType(vec![3, 1, 1]), 2)
+            ])
+        );
+        assert!(report.hands.iter().all(|h| !h.improved()));
+    }
+
+    #[test]
+    fn test_joker_promotions() {
+        let rules = Ruleset::jokers();
+        let report = report(parse_input(INPUT, &rules).unwrap(), &rules);
+        assert_eq!(report.total, 5905);
+        let ktjjt = report.hands.iter().find(|h| h.hand == "KTJJT").unwrap();
+        assert_eq!(ktjjt.rank, 5);
+        assert_eq!(
+            (ktjjt.base_group.to_string(), ktjjt.group.to_string()),
+            ("two pair".to_string(), "four of a kind".to_string())
+        );
+        assert_eq!(report.hands.iter().filter(|h| h.improved()).count(), 3);
+        assert!(report.to_string().contains(
+            "KTJJT\tfour of a kind\trank 5\tbid 220\twinnings 1100\ttwo pair -> four of a kind"
+        ));
+    }
+}
@@ -0,0 +1,171 @@
+use itertools::Itertools;
+use std::fmt;
+
+// Card counts from the largest group down, e.g. `[3, 2]` for a full house.
+// Comparing these lexicographically ranks hands of any length.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HandType(pub Vec<usize>);
+
+impl fmt::Display for HandType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0[..] {
+            [5] => write!(f, "five of a kind"),
+            [4, 1] => write!(f, "four of a kind"),
+            [3, 2] => write!(f, "full house"),
+            [3, 1, 1] => write!(f, "three of a kind"),
+            [2, 2, 1] => write!(f, "two pair"),
+            [2, 1, 1, 1] => write!(f, "one pair"),
+            [1, 1, 1, 1, 1] => write!(f, "high card"),
+            _ => write!(f, "{}", self.0.iter().join("+")),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Ruleset {
+    // Cards from weakest to strongest
+    pub order: Vec<char>,
+    // Cards that join the largest group of other cards when finding the hand type
+    pub wildcards: Vec<char>,
+    // Wildcards break ties below every other card instead of at their place in `order`
+    pub wild_lowest: bool,
+    // Without tie breaks, hands of the same type rank equal
+    pub tie_break: bool,
+    // Checked in order: the first entry whose counts prefix the hand type gives its tier, and
+    // hands matching no entry get tier 0. Within a tier, hand types compare lexicographically,
+    // so an empty table ranks purely by hand type.
+    pub hand_types: Vec<(Vec<usize>, usize)>,
+}
+
+impl Ruleset {
+    pub fn standard() -> Self {
+        Ruleset {
+            order: "23456789TJQKA".chars().collect(),
+            wildcards: Vec::new(),
+            wild_lowest: false,
+            tie_break: true,
+            hand_types: Vec::new(),
+        }
+    }
+
+    pub fn jokers() -> Self {
+        Ruleset {
+            wildcards: vec!['J'],
+            wild_lowest: true,
+            ..Ruleset::standard()
+        }
+    }
+
+    pub fn get_pos(&self, card: char) -> Result<usize, String> {
+        let pos = self
+            .order
+            .iter()
+            .position(|c| *c == card)
+            .ok_or(format!("Unknown card '{card}'"))?;
+        if self.wild_lowest && self.wildcards.contains(&card) {
+            Ok(0)
+        } else {
+            Ok(pos + 1)
+        }
+    }
+
+    pub fn get_tier(&self, hand_type: &HandType) -> usize {
+        self.hand_types
+            .iter()
+            .find(|(prefix, _)| hand_type.0.starts_with(prefix))
+            .map_or(0, |(_, tier)| *tier)
+    }
+
+    pub fn get_group(&self, cards: &[char]) -> HandType {
+        let wild = cards.iter().filter(|c| self.wildcards.contains(c)).count();
+        let mut freq = cards
+            .iter()
+            .filter(|c| !self.wildcards.contains(c))
+            .counts()
+            .into_values()
+            .sorted()
+            .rev()
+            .collect_vec();
+        match freq.first_mut() {
+            Some(x) => *x += wild,
+            None => freq.push(wild),
+        }
+        HandType(freq)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_groups() {
+        let standard = Ruleset::standard();
+        let jokers = Ruleset::jokers();
+        let group = |r: &Ruleset, x: &str| r.get_group(&x.chars().collect_vec()).0;
+        assert_eq!(group(&standard, "KTJJT"), vec![2, 2, 1]);
+        assert_eq!(group(&jokers, "KTJJT"), vec![4, 1]);
+        assert_eq!(group(&jokers, "JJJJJ"), vec![5]);
+        assert_eq!(group(&standard, "23456"), vec![1, 1, 1, 1, 1]);
+        let two_wild = Ruleset {
+            wildcards: vec!['J', '2'],
+            ..Ruleset::jokers()
+        };
+        assert_eq!(group(&two_wild, "2J3A4"), vec![3, 1, 1]);
+    }
+
+    #[test]
+    fn test_hand_type_order() {
+        let types = [
+            "23456", "22345", "22335", "22234", "22233", "22223", "22222",
+        ]
+        .iter()
+        .map(|x| Ruleset::standard().get_group(&x.chars().collect_vec()))
+        .collect_vec();
+        assert!(types.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(types[2].to_string(), "two pair");
+        assert_eq!(HandType(vec![4, 3, 1]).to_string(), "4+3+1");
+        // Longer hands rank by their largest groups first
+        assert!(HandType(vec![4, 1, 1, 1]) > HandType(vec![3, 3, 1]));
+        assert!(HandType(vec![3, 3, 1]) > HandType(vec![3, 2, 2]));
+    }
+
+    #[test]
+    fn test_hand_type_table() {
+        let group = |r: &Ruleset, x: &str| r.get_group(&x.chars().collect_vec());
+        let standard = Ruleset::standard();
+        assert_eq!(standard.get_tier(&group(&standard, "22233")), 0);
+        // Pairs first: a full house, then two pair, then three of a kind, then everything else
+        let rules = Ruleset {
+            hand_types: vec![(vec![3, 2], 3), (vec![2, 2], 2), (vec![3], 1)],
+            ..Ruleset::standard()
+        };
+        assert_eq!(rules.get_tier(&group(&rules, "22233")), 3);
+        assert_eq!(rules.get_tier(&group(&rules, "22334")), 2);
+        assert_eq!(rules.get_tier(&group(&rules, "22234")), 1);
+        assert_eq!(rules.get_tier(&group(&rules, "22224")), 0);
+    }
+
+    #[test]
+    fn test_positions() {
+        assert_eq!(Ruleset::standard().get_pos('J'), Ok(10));
+        assert_eq!(Ruleset::jokers().get_pos('J'), Ok(0));
+        assert_eq!(Ruleset::jokers().get_pos('2'), Ok(1));
+        assert!(Ruleset::standard().get_pos('X').is_err());
+    }
+
+    #[test]
+    fn test_large_alphabet() {
+        let rules = Ruleset {
+            order: (0..300)
+                .map(|i| char::from_u32(0x100 + i).unwrap())
+                .collect(),
+            ..Ruleset::standard()
+        };
+        let lo = rules.order[0];
+        let hi = rules.order[256];
+        assert!(rules.get_pos(hi).unwrap() > rules.get_pos(lo).unwrap());
+        let long = vec![lo; 300];
+        assert_eq!(rules.get_group(&long).0, vec![300]);
+    }
+}
@@ -0,0 +1,33 @@
+use day_08::{
+    cycle::{analyse, cycle_nodes},
+    dot::render_dot,
+    network::{parse_input, Alphabet},
+};
+use std::{env, fs};
+
+fn read_input() -> String {
+    let input: String = fs::read_to_string("src/bin/input.txt").expect("Expected input.txt");
+    input
+}
+
+fn main() {
+    let input: String = read_input();
+    let network =
+        parse_input(input.as_str(), &Alphabet::default()).expect("Expected a valid network");
+    let cycles = network
+        .names
+        .iter()
+        .filter(|x| x.ends_with('A'))
+        .map(|start| {
+            let cycle = analyse(&network, start, |x| x.ends_with('Z'))?;
+            cycle_nodes(&network, start, &cycle)
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .expect("Expected a valid network");
+    let dot = render_dot(&network, &cycles);
+    // Optional output path, otherwise the graph goes to stdout, e.g. `graph network.dot`
+    match env::args().nth(1) {
+        Some(path) => fs::write(&path, dot).expect("Expected to write the dot file"),
+        None => print!("{dot}"),
+    }
+}
@@ -0,0 +1,179 @@
+use crate::network::{Network, NetworkError};
+use std::collections::HashMap;
+
+// A walk is fully described by its current node and position in the instructions,
+// so it enters a cycle as soon as such a state repeats.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    // Steps before the walk enters the cycle
+    pub tail: u64,
+    pub length: u64,
+    // Steps in `0..tail` that end on a target
+    pub tail_hits: Vec<u64>,
+    // Steps in `tail..tail + length` that end on a target; they repeat every `length` steps
+    pub offsets: Vec<u64>,
+}
+
+impl Cycle {
+    pub fn first_hit(&self) -> Option<u64> {
+        self.tail_hits.first().or(self.offsets.first()).copied()
+    }
+
+    // Whether the walk is on a target after `step` steps
+    pub fn contains(&self, step: u64) -> bool {
+        if step < self.tail {
+            self.tail_hits.contains(&step)
+        } else {
+            let offset = self.tail + (step - self.tail) % self.length;
+            self.offsets.contains(&offset)
+        }
+    }
+}
+
+pub fn analyse(
+    network: &Network,
+    start: &str,
+    is_target: impl Fn(&str) -> bool,
+) -> Result<Cycle, NetworkError> {
+    let len = network.instructions.len();
+    let targets = network
+        .names
+        .iter()
+        .map(|x| is_target(x))
+        .collect::<Vec<_>>();
+    let mut seen: HashMap<(usize, usize), u64> = HashMap::new();
+    let mut hits = Vec::new();
+    let mut node = network.id(start)?;
+    let mut step: u64 = 0;
+    loop {
+        let state = (node, step as usize % len);
+        if let Some(tail) = seen.get(&state) {
+            let (tail_hits, offsets) = hits.iter().partition(|x| **x < *tail);
+            return Ok(Cycle {
+                tail: *tail,
+                length: step - tail,
+                tail_hits,
+                offsets,
+            });
+        }
+        seen.insert(state, step);
+        if targets[node] {
+            hits.push(step);
+        }
+        node = network.next(node, step as usize);
+        step += 1;
+    }
+}
+
+// The nodes visited once round the cycle, ending back on the first one
+pub fn cycle_nodes(
+    network: &Network,
+    start: &str,
+    cycle: &Cycle,
+) -> Result<Vec<usize>, NetworkError> {
+    let mut node = network.id(start)?;
+    for step in 0..cycle.tail {
+        node = network.next(node, step as usize);
+    }
+    let mut nodes = vec![node];
+    for step in cycle.tail..cycle.tail + cycle.length {
+        node = network.next(node, step as usize);
+        nodes.push(node);
+    }
+    Ok(nodes)
+}
+
+// Fewest steps from `start` to a target, or an error if the walk never gets there
+pub fn steps_to(
+    network: &Network,
+    start: &str,
+    is_target: impl Fn(&str) -> bool,
+) -> Result<u64, NetworkError> {
+    let cycle = analyse(network, start, is_target)?;
+    cycle.first_hit().ok_or(NetworkError::Unreachable {
+        start: start.to_string(),
+        steps: cycle.tail + cycle.length,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::{parse_input, Alphabet};
+
+    const GHOSTS: &str = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
+
+    #[test]
+    fn test_analyse() {
+        let network = parse_input(GHOSTS, &Alphabet::default()).unwrap();
+        let ends_z = |x: &str| x.ends_with('Z');
+        assert_eq!(
+            analyse(&network, "11A", ends_z).unwrap(),
+            Cycle {
+                tail: 1,
+                length: 2,
+                tail_hits: vec![],
+                offsets: vec![2],
+            }
+        );
+        assert_eq!(
+            analyse(&network, "22A", ends_z).unwrap(),
+            Cycle {
+                tail: 1,
+                length: 6,
+                tail_hits: vec![],
+                offsets: vec![3, 6],
+            }
+        );
+        assert_eq!(
+            analyse(&network, "XXX", ends_z).unwrap(),
+            Cycle {
+                tail: 0,
+                length: 2,
+                tail_hits: vec![],
+                offsets: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn test_cycle_nodes() {
+        let network = parse_input(GHOSTS, &Alphabet::default()).unwrap();
+        let cycle = analyse(&network, "11A", |x| x.ends_with('Z')).unwrap();
+        let nodes = cycle_nodes(&network, "11A", &cycle).unwrap();
+        let names = nodes.iter().map(|x| network.names[*x]).collect::<Vec<_>>();
+        assert_eq!(names, vec!["11B", "11Z", "11B"]);
+    }
+
+    #[test]
+    fn test_steps_to() {
+        let network = parse_input(
+            "LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)",
+            &Alphabet::default(),
+        )
+        .unwrap();
+        assert_eq!(steps_to(&network, "AAA", |x| x == "ZZZ"), Ok(6));
+        assert_eq!(steps_to(&network, "ZZZ", |x| x == "ZZZ"), Ok(0));
+        let network = parse_input(GHOSTS, &Alphabet::default()).unwrap();
+        assert_eq!(
+            steps_to(&network, "XXX", |x| x == "11Z"),
+            Err(NetworkError::Unreachable {
+                start: "XXX".to_string(),
+                steps: 2
+            })
+        );
+        assert_eq!(
+            steps_to(&network, "QQQ", |x| x == "11Z"),
+            Err(NetworkError::UnknownNode("QQQ".to_string()))
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use crate::network::Network;
+use std::{collections::HashSet, fmt::Write};
+
+const START_COLOUR: &str = "#2e7d32";
+const END_COLOUR: &str = "#c62828";
+const CYCLE_COLOUR: &str = "#1565c0";
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+// Graphviz source for the network, e.g. `dot -Tsvg network.dot -o network.svg`.
+// Each cycle is a list of nodes where consecutive nodes are joined by an edge.
+pub fn render_dot(network: &Network, cycles: &[Vec<usize>]) -> String {
+    let on_cycle = cycles
+        .iter()
+        .flat_map(|c| c.windows(2).map(|w| (w[0], w[1])))
+        .collect::<HashSet<_>>();
+    let mut dot = String::new();
+    writeln!(dot, "digraph network {{").unwrap();
+    writeln!(dot, "  node [shape=circle, fontname=monospace];").unwrap();
+    for (id, name) in network.names.iter().enumerate() {
+        let colour = if name.ends_with('A') {
+            Some(START_COLOUR)
+        } else if name.ends_with('Z') {
+            Some(END_COLOUR)
+        } else {
+            None
+        };
+        match colour {
+            Some(colour) => writeln!(
+                dot,
+                r#"  n{id} [label="{name}", style=filled, fillcolor="{colour}", fontcolor=white];"#
+            ),
+            None => writeln!(dot, r#"  n{id} [label="{name}"];"#),
+        }
+        .unwrap();
+    }
+    for id in 0..network.names.len() {
+        // Instructions leading to the same successor share one edge
+        let mut labels: Vec<(usize, String)> = Vec::new();
+        for (to, symbol) in network
+            .successors(id)
+            .iter()
+            .zip(network.alphabet.symbols.iter())
+        {
+            match labels.iter_mut().find(|(x, _)| x == to) {
+                Some((_, label)) => label.push(*symbol),
+                None => labels.push((*to, symbol.to_string())),
+            }
+        }
+        for (to, label) in labels {
+            let label = escape(&label);
+            if on_cycle.contains(&(id, to)) {
+                writeln!(
+                    dot,
+                    r#"  n{id} -> n{to} [label="{label}", color="{CYCLE_COLOUR}", penwidth=2];"#
+                )
+            } else {
+                writeln!(dot, r#"  n{id} -> n{to} [label="{label}"];"#)
+            }
+            .unwrap();
+        }
+    }
+    writeln!(dot, "}}").unwrap();
+    dot
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cycle::{analyse, cycle_nodes},
+        network::{parse_input, Alphabet},
+    };
+
+    #[test]
+    fn test_render_dot() {
+        let network = parse_input(
+            "LR\n\n11A = (11B, XXX)\n11B = (XXX, 11Z)\n11Z = (11B, XXX)\nXXX = (XXX, XXX)",
+            &Alphabet::default(),
+        )
+        .unwrap();
+        let cycle = analyse(&network, "11A", |x| x.ends_with('Z')).unwrap();
+        let nodes = cycle_nodes(&network, "11A", &cycle).unwrap();
+        let dot = render_dot(&network, &[nodes]);
+        assert!(dot.starts_with("digraph network {"));
+        assert!(dot.trim_end().ends_with('}'));
+        assert_eq!(dot.matches(START_COLOUR).count(), 1);
+        assert_eq!(dot.matches(END_COLOUR).count(), 1);
+        assert_eq!(dot.matches(CYCLE_COLOUR).count(), 2);
+        assert!(dot.contains(r#"n3 -> n3 [label="LR"];"#));
+        assert!(dot.contains(r##"n1 -> n2 [label="R", color="#1565c0", penwidth=2];"##));
+    }
+    #[test]
+    fn test_render_dot_alphabet() {
+        let alphabet = "<^>".parse::<Alphabet>().unwrap();
+        let network = parse_input(
+            "<^>\n\nAAA = (ZZZ, AAA, ZZZ)\nZZZ = (ZZZ, ZZZ, ZZZ)",
+            &alphabet,
+        )
+        .unwrap();
+        let dot = render_dot(&network, &[]);
+        assert!(dot.contains(r#"n0 -> n1 [label="<>"];"#));
+        assert!(dot.contains(r#"n0 -> n0 [label="^"];"#));
+        assert!(dot.contains(r#"n1 -> n1 [label="<^>"];"#));
+    }
+}
@@ -0,0 +1,4 @@
+pub mod cycle;
+pub mod dot;
+pub mod network;
+pub mod sync;
@@ -0,0 +1,298 @@
+use nom::{
+    bytes::complete::{is_not, tag},
+    character::complete::{alphanumeric1, line_ending, space0},
+    combinator::all_consuming,
+    multi::separated_list1,
+    sequence::{delimited, pair, preceded, separated_pair},
+    IResult,
+};
+use std::{collections::HashMap, fmt, str::FromStr};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NetworkError {
+    UnknownNode(String),
+    InvalidInstruction {
+        instruction: char,
+        position: usize,
+    },
+    Unreachable {
+        start: String,
+        steps: u64,
+    },
+    Successors {
+        node: String,
+        count: usize,
+        expected: usize,
+    },
+    DuplicateSymbol(char),
+    EmptyInstructions,
+    DuplicateNode(String),
+    Syntax(String),
+}
+
+impl fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NetworkError::UnknownNode(node) => write!(f, "Unknown node '{node}'"),
+            NetworkError::InvalidInstruction {
+                instruction,
+                position,
+            } => write!(
+                f,
+                "Invalid instruction '{instruction}' at position {position}"
+            ),
+            NetworkError::Unreachable { start, steps } => write!(
+                f,
+                "No target reachable from {start}, the walk repeats after {steps} steps"
+            ),
+            NetworkError::Successors {
+                node,
+                count,
+                expected,
+            } => write!(
+                f,
+                "Node {node} has {count} successors but the alphabet needs {expected}"
+            ),
+            NetworkError::DuplicateSymbol(symbol) => {
+                write!(f, "Instruction '{symbol}' appears twice in the alphabet")
+            }
+            NetworkError::EmptyInstructions => write!(f, "No instructions to follow"),
+            NetworkError::DuplicateNode(node) => write!(f, "Node {node} is defined twice"),
+            NetworkError::Syntax(message) => write!(f, "Invalid network: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for NetworkError {}
+
+// The instruction symbols in successor order, e.g. "LR" follows the first successor on L
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Alphabet {
+    pub symbols: Vec<char>,
+}
+
+impl Alphabet {
+    pub fn index(&self, symbol: char) -> Option<usize> {
+        self.symbols.iter().position(|x| *x == symbol)
+    }
+
+    pub fn arity(&self) -> usize {
+        self.symbols.len()
+    }
+}
+
+impl Default for Alphabet {
+    fn default() -> Self {
+        Alphabet {
+            symbols: vec!['L', 'R'],
+        }
+    }
+}
+
+impl FromStr for Alphabet {
+    type Err = NetworkError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut symbols = Vec::new();
+        for symbol in s.chars() {
+            if symbols.contains(&symbol) {
+                return Err(NetworkError::DuplicateSymbol(symbol));
+            }
+            symbols.push(symbol);
+        }
+        Ok(Alphabet { symbols })
+    }
+}
+
+// Nodes are interned to dense ids in order of definition, and the successors of node `n`
+// are `edges[n * arity..(n + 1) * arity]`, so a step is an array index
+pub struct Network<'a> {
+    pub alphabet: Alphabet,
+    pub instructions: Vec<usize>,
+    pub names: Vec<&'a str>,
+    pub edges: Vec<usize>,
+    ids: HashMap<&'a str, usize>,
+}
+
+impl<'a> Network<'a> {
+    pub fn new(
+        directions: &str,
+        nodes: Vec<(&'a str, Vec<&'a str>)>,
+        alphabet: &Alphabet,
+    ) -> Result<Self, NetworkError> {
+        if directions.is_empty() {
+            return Err(NetworkError::EmptyInstructions);
+        }
+        let instructions = directions
+            .chars()
+            .enumerate()
+            .map(|(position, instruction)| {
+                alphabet
+                    .index(instruction)
+                    .ok_or(NetworkError::InvalidInstruction {
+                        instruction,
+                        position,
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let names = nodes.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+        let mut ids = HashMap::with_capacity(names.len());
+        for (id, name) in names.iter().enumerate() {
+            if ids.insert(*name, id).is_some() {
+                return Err(NetworkError::DuplicateNode(name.to_string()));
+            }
+        }
+        let arity = alphabet.arity();
+        let mut edges = Vec::with_capacity(nodes.len() * arity);
+        for (node, successors) in nodes.iter() {
+            if successors.len() < arity {
+                return Err(NetworkError::Successors {
+                    node: node.to_string(),
+                    count: successors.len(),
+                    expected: arity,
+                });
+            }
+            for successor in successors[..arity].iter() {
+                let id = ids
+                    .get(successor)
+                    .ok_or(NetworkError::UnknownNode(successor.to_string()))?;
+                edges.push(*id);
+            }
+        }
+        Ok(Network {
+            alphabet: alphabet.clone(),
+            instructions,
+            names,
+            edges,
+            ids,
+        })
+    }
+
+    pub fn id(&self, name: &str) -> Result<usize, NetworkError> {
+        self.ids
+            .get(name)
+            .copied()
+            .ok_or(NetworkError::UnknownNode(name.to_string()))
+    }
+
+    pub fn successors(&self, node: usize) -> &[usize] {
+        let arity = self.alphabet.arity();
+        &self.edges[node * arity..(node + 1) * arity]
+    }
+
+    pub fn next(&self, node: usize, step: usize) -> usize {
+        let instruction = self.instructions[step % self.instructions.len()];
+        self.edges[node * self.alphabet.arity() + instruction]
+    }
+}
+
+fn parse_directions(input: &str) -> IResult<&str, &str> {
+    preceded(space0, is_not(" \r\n"))(input)
+}
+
+fn parse_node(input: &str) -> IResult<&str, (&str, Vec<&str>)> {
+    preceded(
+        space0,
+        separated_pair(
+            alphanumeric1,
+            tag(" = "),
+            delimited(
+                tag("("),
+                separated_list1(tag(", "), alphanumeric1),
+                tag(")"),
+            ),
+        ),
+    )(input)
+}
+
+fn parse_block(input: &str) -> IResult<&str, Vec<(&str, Vec<&str>)>> {
+    separated_list1(line_ending, parse_node)(input)
+}
+
+pub fn parse_input<'a>(input: &'a str, alphabet: &Alphabet) -> Result<Network<'a>, NetworkError> {
+    let (_, (directions, nodes)) = all_consuming(separated_pair(
+        parse_directions,
+        pair(line_ending, line_ending),
+        parse_block,
+    ))(input.trim_end())
+    .map_err(|e| NetworkError::Syntax(e.to_string()))?;
+    Network::new(directions, nodes, alphabet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interning() {
+        let network = parse_input(
+            "LR\n\nAAA = (BBB, ZZZ)\nBBB = (AAA, BBB)\nZZZ = (ZZZ, ZZZ)",
+            &Alphabet::default(),
+        )
+        .unwrap();
+        assert_eq!(network.names, vec!["AAA", "BBB", "ZZZ"]);
+        assert_eq!(network.edges, vec![1, 2, 0, 1, 2, 2]);
+        assert_eq!(network.successors(1), &[0, 1]);
+        assert_eq!(network.id("ZZZ"), Ok(2));
+        assert_eq!(network.next(0, 1), 2);
+        assert_eq!(network.next(0, 2), 1);
+    }
+
+    #[test]
+    fn test_alphabet() {
+        let alphabet = "<^>".parse::<Alphabet>().unwrap();
+        let network = parse_input(
+            ">><^\n\nAAA = (BBB, CCC, AAA)\nBBB = (AAA, BBB, CCC, AAA)\nCCC = (CCC, CCC, CCC)",
+            &alphabet,
+        )
+        .unwrap();
+        assert_eq!(network.instructions, vec![2, 2, 0, 1]);
+        assert_eq!(network.successors(1), &[0, 1, 2]);
+        assert_eq!(network.next(1, 3), 1);
+        assert_eq!(
+            "LRL".parse::<Alphabet>(),
+            Err(NetworkError::DuplicateSymbol('L'))
+        );
+    }
+
+    #[test]
+    fn test_invalid_network() {
+        let alphabet = Alphabet::default();
+        assert_eq!(
+            parse_input("LR\n\nAAA = (BBB, CCC)\nBBB = (AAA, BBB)", &alphabet).err(),
+            Some(NetworkError::UnknownNode("CCC".to_string()))
+        );
+        assert_eq!(
+            parse_input("LR\n\nAAA = (AAA)", &alphabet).err(),
+            Some(NetworkError::Successors {
+                node: "AAA".to_string(),
+                count: 1,
+                expected: 2
+            })
+        );
+        assert_eq!(
+            parse_input("LRLX\n\nAAA = (AAA, AAA)", &alphabet).err(),
+            Some(NetworkError::InvalidInstruction {
+                instruction: 'X',
+                position: 3
+            })
+        );
+        assert_eq!(
+            parse_input("LR\n\nAAA = (AAA, AAA)\nAAA = (AAA, AAA)", &alphabet).err(),
+            Some(NetworkError::DuplicateNode("AAA".to_string()))
+        );
+        assert!(matches!(
+            parse_input("LR\nAAA = (AAA, AAA)", &alphabet),
+            Err(NetworkError::Syntax(_))
+        ));
+        assert!(matches!(
+            parse_input("LR\n\nAAA = (AAA, AAA)\nBBB = BBB", &alphabet),
+            Err(NetworkError::Syntax(_))
+        ));
+        assert!(parse_input("LR\r\n\r\nAAA = (AAA, AAA)\r\n", &alphabet).is_ok());
+        assert_eq!(
+            Network::new("", vec![("AAA", vec!["AAA", "AAA"])], &alphabet).err(),
+            Some(NetworkError::EmptyInstructions)
+        );
+    }
+}
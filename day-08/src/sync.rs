@@ -0,0 +1,147 @@
+use crate::cycle::Cycle;
+use num::{integer::Integer, BigInt, BigUint, One, Zero};
+
+// All steps `t` with `t % modulus == residue`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Congruence {
+    pub residue: BigInt,
+    pub modulus: BigInt,
+}
+
+impl Congruence {
+    pub fn new(residue: BigInt, modulus: BigInt) -> Self {
+        Congruence {
+            residue: residue.mod_floor(&modulus),
+            modulus,
+        }
+    }
+
+    // Generalised CRT: the moduli need not be coprime, in which case there may be no solution
+    pub fn combine(&self, other: &Congruence) -> Option<Congruence> {
+        let gcd = self.modulus.extended_gcd(&other.modulus);
+        let diff = &other.residue - &self.residue;
+        if !diff.is_multiple_of(&gcd.gcd) {
+            return None;
+        }
+        let lcm = &self.modulus / &gcd.gcd * &other.modulus;
+        // self.modulus * gcd.x == gcd.gcd (mod other.modulus)
+        let k = (diff / &gcd.gcd * gcd.x).mod_floor(&(&other.modulus / &gcd.gcd));
+        Some(Congruence::new(&self.residue + &self.modulus * k, lcm))
+    }
+
+    // Smallest step in the congruence which is at least `min`
+    pub fn first_from(&self, min: &BigInt) -> BigInt {
+        min + (&self.residue - min).mod_floor(&self.modulus)
+    }
+}
+
+fn congruences(cycle: &Cycle) -> Vec<Congruence> {
+    cycle
+        .offsets
+        .iter()
+        .map(|o| Congruence::new(BigInt::from(*o), BigInt::from(cycle.length)))
+        .collect()
+}
+
+// The first step at which every walk is on a target at once, or None when that never happens
+pub fn synchronise(cycles: &[Cycle]) -> Option<BigUint> {
+    // Steps inside some tail only happen once, so they can be checked one by one
+    let early = cycles
+        .iter()
+        .flat_map(|c| c.tail_hits.iter())
+        .filter(|t| cycles.iter().all(|c| c.contains(**t)))
+        .min()
+        .map(|t| BigUint::from(*t));
+    if early.is_some() {
+        return early;
+    }
+    // Past every tail, each walk is on a target exactly on the union of its congruences
+    let mut combined = vec![Congruence::new(BigInt::zero(), BigInt::one())];
+    for cycle in cycles {
+        let next = congruences(cycle);
+        combined = combined
+            .iter()
+            .flat_map(|a| next.iter().filter_map(|b| a.combine(b)))
+            .collect();
+        combined.sort_by(|a, b| a.residue.cmp(&b.residue));
+        combined.dedup();
+        if combined.is_empty() {
+            return None;
+        }
+    }
+    let min = BigInt::from(cycles.iter().map(|c| c.tail).max().unwrap_or(0));
+    combined
+        .iter()
+        .map(|c| c.first_from(&min))
+        .min()
+        .and_then(|t| t.to_biguint())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_utils::{for_all, Lcg};
+
+    fn cycle(tail: u64, length: u64, tail_hits: &[u64], offsets: &[u64]) -> Cycle {
+        Cycle {
+            tail,
+            length,
+            tail_hits: tail_hits.to_vec(),
+            offsets: offsets.to_vec(),
+        }
+    }
+
+    // Checks every step up to `limit` directly
+    fn brute_force(cycles: &[Cycle], limit: u64) -> Option<u64> {
+        (0..limit).find(|t| cycles.iter().all(|c| c.contains(*t)))
+    }
+
+    #[test]
+    fn test_combine() {
+        let c = |r: i64, m: i64| Congruence::new(BigInt::from(r), BigInt::from(m));
+        assert_eq!(c(2, 3).combine(&c(3, 5)), Some(c(8, 15)));
+        assert_eq!(c(2, 4).combine(&c(4, 6)), Some(c(10, 12)));
+        assert_eq!(c(1, 4).combine(&c(2, 6)), None);
+        assert_eq!(c(-1, 4).residue, BigInt::from(3));
+    }
+
+    #[test]
+    fn test_synchronise() {
+        let ghosts = [cycle(1, 2, &[], &[2]), cycle(1, 6, &[], &[3, 6])];
+        assert_eq!(synchronise(&ghosts), Some(BigUint::from(6u32)));
+        // Different parities never meet
+        let ghosts = [cycle(0, 2, &[], &[0]), cycle(0, 4, &[], &[1, 3])];
+        assert_eq!(synchronise(&ghosts), None);
+        // A ghost which leaves its only target behind in its tail
+        let ghosts = [cycle(4, 3, &[2], &[]), cycle(0, 2, &[], &[0])];
+        assert_eq!(synchronise(&ghosts), Some(BigUint::from(2u32)));
+        let ghosts = [cycle(4, 3, &[3], &[]), cycle(0, 2, &[], &[0])];
+        assert_eq!(synchronise(&ghosts), None);
+        // The earliest meeting is before the longest tail ends
+        let ghosts = [cycle(10, 5, &[7], &[12]), cycle(0, 7, &[], &[0])];
+        assert_eq!(synchronise(&ghosts), Some(BigUint::from(7u32)));
+        assert_eq!(synchronise(&[]), Some(BigUint::zero()));
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let ghosts = |rng: &mut Lcg| {
+            (0..rng.range(1, 4))
+                .map(|_| {
+                    let tail = rng.below(6);
+                    let length = 1 + rng.below(8);
+                    let tail_hits = (0..tail).filter(|_| rng.one_in(4)).collect::<Vec<_>>();
+                    let offsets = (tail..tail + length)
+                        .filter(|_| rng.one_in(3))
+                        .collect::<Vec<_>>();
+                    cycle(tail, length, &tail_hits, &offsets)
+                })
+                .collect::<Vec<_>>()
+        };
+        for_all(42, 500, ghosts, |ghosts| {
+            // Past every tail the pattern repeats with the lcm of the lengths, at most 840
+            let expected = brute_force(ghosts, 6 + 840).map(BigUint::from);
+            assert_eq!(synchronise(ghosts), expected);
+        });
+    }
+}
@@ -0,0 +1,22 @@
+use day_09::{history::parse_input, polynomial::fit};
+use std::fs;
+
+fn read_input() -> String {
+    let input: String = fs::read_to_string("src/bin/input.txt").expect("Expected input.txt");
+    input
+}
+
+fn main() {
+    let input: String = read_input();
+    let histories = parse_input(input.as_str()).expect("Expected valid readings");
+    for (i, history) in histories.iter().enumerate() {
+        let fit = fit(history);
+        let degree = fit.degree().map_or("-".to_string(), |d| d.to_string());
+        let flag = if fit.is_degenerate() {
+            "\tdegenerate"
+        } else {
+            ""
+        };
+        println!("{}\t{degree}\t{}{flag}", i + 1, fit.polynomial)
+    }
+}
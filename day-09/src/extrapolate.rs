@@ -0,0 +1,185 @@
+use itertools::Itertools;
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExtrapolationError {
+    Empty,
+    // One reading says nothing about how the sequence changes
+    SingleElement,
+    // The differences ran out before a row of zeros, so the history does not pin down the sequence
+    NoZeroRow { len: usize },
+    Overflow,
+}
+
+impl fmt::Display for ExtrapolationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExtrapolationError::Empty => write!(f, "The history has no readings"),
+            ExtrapolationError::SingleElement => write!(f, "The history has a single reading"),
+            ExtrapolationError::NoZeroRow { len } => write!(
+                f,
+                "The differences of {len} readings never reach a row of zeros"
+            ),
+            ExtrapolationError::Overflow => write!(f, "The values do not fit in an i64"),
+        }
+    }
+}
+
+impl std::error::Error for ExtrapolationError {}
+
+// The rows of successive differences of a history, down to the last row which is not all zeros
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DifferenceTable {
+    pub rows: Vec<Vec<i64>>,
+    pub len: usize,
+}
+
+impl DifferenceTable {
+    pub fn new(history: &[i64]) -> Result<Self, ExtrapolationError> {
+        match history.len() {
+            0 => return Err(ExtrapolationError::Empty),
+            1 => return Err(ExtrapolationError::SingleElement),
+            _ => (),
+        }
+        let mut rows = Vec::new();
+        let mut row = history.to_vec();
+        while row.iter().any(|x| *x != 0) {
+            let next = row
+                .iter()
+                .tuple_windows()
+                .map(|(a, b)| b.checked_sub(*a).ok_or(ExtrapolationError::Overflow))
+                .collect::<Result<Vec<_>, _>>()?;
+            rows.push(row);
+            row = next;
+        }
+        if row.is_empty() {
+            return Err(ExtrapolationError::NoZeroRow { len: history.len() });
+        }
+        Ok(DifferenceTable {
+            rows,
+            len: history.len(),
+        })
+    }
+
+    // Newton's forward difference formula: f(x) = sum over j of C(x, j) * (first entry of row j).
+    // Any integer x works, with x < 0 before the history and x >= len after it.
+    // The sum is kept in i128 so that only a result outside i64 is an overflow.
+    pub fn value_at(&self, x: i64) -> Result<i64, ExtrapolationError> {
+        let mut binomial: i128 = 1;
+        let mut value: i128 = 0;
+        for (j, row) in self.rows.iter().enumerate() {
+            value = binomial
+                .checked_mul(row[0] as i128)
+                .and_then(|term| value.checked_add(term))
+                .ok_or(ExtrapolationError::Overflow)?;
+            // C(x, j + 1) = C(x, j) * (x - j) / (j + 1), always an exact division
+            binomial = binomial
+                .checked_mul(x as i128 - j as i128)
+                .ok_or(ExtrapolationError::Overflow)?
+                / (j as i128 + 1);
+        }
+        i64::try_from(value).map_err(|_| ExtrapolationError::Overflow)
+    }
+
+    // The `k` values following the history
+    pub fn forward(&self, k: usize) -> Result<Vec<i64>, ExtrapolationError> {
+        (0..k as i64)
+            .map(|i| self.value_at(self.len as i64 + i))
+            .collect()
+    }
+
+    // The `k` values preceding the history, nearest first
+    pub fn backward(&self, k: usize) -> Result<Vec<i64>, ExtrapolationError> {
+        (1..=k as i64).map(|i| self.value_at(-i)).collect()
+    }
+
+    pub fn next(&self) -> Result<i64, ExtrapolationError> {
+        self.value_at(self.len as i64)
+    }
+
+    pub fn previous(&self) -> Result<i64, ExtrapolationError> {
+        self.value_at(-1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_utils::{for_all, Lcg};
+
+    // Extends the history one step at a time by adding up the last column of differences
+    fn extend(history: &[i64], k: usize) -> Vec<i64> {
+        let mut values = history.to_vec();
+        for _ in 0..k {
+            let mut row = values.clone();
+            let mut next = 0;
+            while row.iter().any(|x| *x != 0) {
+                next += row.last().unwrap();
+                row = row.iter().tuple_windows().map(|(a, b)| b - a).collect();
+            }
+            values.push(next);
+        }
+        values[history.len()..].to_vec()
+    }
+
+    #[test]
+    fn test_example() {
+        let table = DifferenceTable::new(&[10, 13, 16, 21, 30, 45]).unwrap();
+        assert_eq!(table.rows.len(), 4);
+        assert_eq!(table.next(), Ok(68));
+        assert_eq!(table.previous(), Ok(5));
+        assert_eq!(table.forward(3), Ok(vec![68, 101, 146]));
+        assert_eq!(table.backward(3), Ok(vec![5, -4, -19]));
+        assert_eq!(
+            DifferenceTable::new(&[0, 3, 6, 9, 12, 15])
+                .unwrap()
+                .previous(),
+            Ok(-3)
+        );
+        assert_eq!(
+            DifferenceTable::new(&[0, 0]).unwrap().forward(2),
+            Ok(vec![0, 0])
+        );
+        assert_eq!(
+            DifferenceTable::new(&[1, 2, 4]),
+            Err(ExtrapolationError::NoZeroRow { len: 3 })
+        );
+    }
+
+    #[test]
+    fn test_matches_extend() {
+        // Random cubics, compared with extending the history step by step
+        let case = |rng: &mut Lcg| {
+            let coefficients = (0..4).map(|_| rng.range(-10, 11)).collect_vec();
+            let len = rng.range(5, 10);
+            (coefficients, len)
+        };
+        for_all(9, 200, case, |(coefficients, len)| {
+            let f = |x: i64| coefficients.iter().rev().fold(0, |acc, c| acc * x + c);
+            let history = (0..*len).map(f).collect_vec();
+            let table = DifferenceTable::new(&history).unwrap();
+            assert_eq!(table.forward(10).unwrap(), extend(&history, 10));
+            let backward = (1..=10).map(|i| f(-i)).collect_vec();
+            assert_eq!(table.backward(10).unwrap(), backward);
+        });
+    }
+
+    #[test]
+    fn test_degenerate() {
+        assert_eq!(DifferenceTable::new(&[]), Err(ExtrapolationError::Empty));
+        assert_eq!(
+            DifferenceTable::new(&[0]),
+            Err(ExtrapolationError::SingleElement)
+        );
+        assert_eq!(
+            DifferenceTable::new(&[i64::MIN, i64::MAX]),
+            Err(ExtrapolationError::Overflow)
+        );
+        let table = DifferenceTable::new(&[i64::MAX - 3, i64::MAX - 2, i64::MAX - 1]).unwrap();
+        assert_eq!(table.next(), Ok(i64::MAX));
+        assert_eq!(table.forward(2), Err(ExtrapolationError::Overflow));
+        let squares = DifferenceTable::new(&[0, 1, 4, 9]).unwrap();
+        assert_eq!(squares.value_at(1 << 31), Ok(1 << 62));
+        assert_eq!(squares.value_at(1 << 32), Err(ExtrapolationError::Overflow));
+    }
+}
@@ -0,0 +1,35 @@
+use nom::{
+    character::complete::{i64, line_ending, space0, space1},
+    combinator::all_consuming,
+    multi::separated_list1,
+    sequence::preceded,
+    IResult,
+};
+
+fn parse_line(input: &str) -> IResult<&str, Vec<i64>> {
+    preceded(space0, separated_list1(space1, i64))(input)
+}
+
+fn parse_block(input: &str) -> IResult<&str, Vec<Vec<i64>>> {
+    separated_list1(line_ending, parse_line)(input)
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Vec<i64>>, String> {
+    all_consuming(parse_block)(input.trim_end())
+        .map(|(_, histories)| histories)
+        .map_err(|e| format!("Invalid readings: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_input() {
+        let expected = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        assert_eq!(parse_input("1 2 3\n  4 5 6\n"), Ok(expected.clone()));
+        assert_eq!(parse_input("1 2 3\r\n4 5 6\r\n"), Ok(expected));
+        assert!(parse_input("1 2 3\n4 x 6\n7 8 9").is_err());
+        assert!(parse_input("").is_err());
+    }
+}
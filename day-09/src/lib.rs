@@ -0,0 +1,3 @@
+pub mod extrapolate;
+pub mod history;
+pub mod polynomial;
@@ -0,0 +1,167 @@
+use itertools::Itertools;
+use num::{rational::BigRational, BigInt, One, Signed, Zero};
+use std::fmt;
+
+// Exact coefficients, constant term first, without trailing zeros
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Polynomial {
+    pub coefficients: Vec<BigRational>,
+}
+
+impl Polynomial {
+    fn new(mut coefficients: Vec<BigRational>) -> Self {
+        while coefficients.last().is_some_and(|c| c.is_zero()) {
+            coefficients.pop();
+        }
+        Polynomial { coefficients }
+    }
+
+    // None for the zero polynomial
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.len().checked_sub(1)
+    }
+
+    pub fn evaluate(&self, x: &BigRational) -> BigRational {
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(BigRational::zero(), |acc, c| acc * x + c)
+    }
+}
+
+impl fmt::Display for Polynomial {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let terms = self
+            .coefficients
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, c)| !c.is_zero())
+            .collect_vec();
+        if terms.is_empty() {
+            return write!(f, "0");
+        }
+        for (i, (power, c)) in terms.into_iter().enumerate() {
+            match (i, c.is_negative()) {
+                (0, true) => write!(f, "-")?,
+                (0, false) => (),
+                (_, true) => write!(f, " - ")?,
+                (_, false) => write!(f, " + ")?,
+            }
+            let c = c.abs();
+            match power {
+                0 => write!(f, "{c}")?,
+                _ if c.is_one() => write!(f, "x")?,
+                _ => write!(f, "{c}*x")?,
+            }
+            if power > 1 {
+                write!(f, "^{power}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fit {
+    pub polynomial: Polynomial,
+    pub readings: usize,
+}
+
+impl Fit {
+    pub fn degree(&self) -> Option<usize> {
+        self.polynomial.degree()
+    }
+
+    // Every reading went into a coefficient, so any history fits and the prediction is unsupported.
+    // The zero polynomial counts as degree 0, like any other constant.
+    pub fn is_degenerate(&self) -> bool {
+        self.degree().unwrap_or(0) + 1 >= self.readings
+    }
+}
+
+// The lowest degree polynomial through (0, history[0]), (1, history[1]), ...
+// Newton's forward difference formula gives f(x) = sum over j of d_j * x(x-1)...(x-j+1) / j!,
+// where d_j is the first entry of the j-th row of differences.
+pub fn fit(history: &[i64]) -> Fit {
+    let mut coefficients = vec![BigRational::zero(); history.len()];
+    let mut row = history.iter().map(|x| BigInt::from(*x)).collect_vec();
+    // x(x-1)...(x-j+1) with integer coefficients, and j!
+    let mut falling = vec![BigInt::one()];
+    let mut factorial = BigInt::one();
+    for j in 0..history.len() {
+        for (power, c) in falling.iter().enumerate() {
+            coefficients[power] += BigRational::new(&row[0] * c, factorial.clone());
+        }
+        row = row.iter().tuple_windows().map(|(a, b)| b - a).collect();
+        // Multiply by (x - j)
+        let j = BigInt::from(j);
+        let mut next = vec![BigInt::zero(); falling.len() + 1];
+        for (power, c) in falling.iter().enumerate() {
+            next[power + 1] += c;
+            next[power] -= c * &j;
+        }
+        falling = next;
+        factorial *= j + 1;
+    }
+    Fit {
+        polynomial: Polynomial::new(coefficients),
+        readings: history.len(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extrapolate::DifferenceTable;
+
+    fn ratio(n: i64, d: i64) -> BigRational {
+        BigRational::new(BigInt::from(n), BigInt::from(d))
+    }
+
+    #[test]
+    fn test_fit() {
+        let line = fit(&[0, 3, 6, 9, 12, 15]);
+        assert_eq!(line.degree(), Some(1));
+        assert_eq!(line.polynomial.coefficients, vec![ratio(0, 1), ratio(3, 1)]);
+        assert_eq!(line.polynomial.to_string(), "3*x");
+        let triangle = fit(&[1, 3, 6, 10, 15, 21]);
+        assert_eq!(
+            triangle.polynomial.coefficients,
+            vec![ratio(1, 1), ratio(3, 2), ratio(1, 2)]
+        );
+        assert_eq!(triangle.polynomial.to_string(), "1/2*x^2 + 3/2*x + 1");
+        assert!(!triangle.is_degenerate());
+        let cubic = fit(&[10, 13, 16, 21, 30, 45]);
+        assert_eq!(cubic.degree(), Some(3));
+        assert_eq!(cubic.polynomial.evaluate(&ratio(6, 1)), ratio(68, 1));
+        assert_eq!(cubic.polynomial.evaluate(&ratio(-1, 1)), ratio(5, 1));
+        assert_eq!(fit(&[-1, -2]).polynomial.to_string(), "-x - 1");
+        assert_eq!(fit(&[0, 0, 0]).degree(), None);
+        assert_eq!(fit(&[0, 0, 0]).polynomial.to_string(), "0");
+    }
+
+    #[test]
+    fn test_degenerate() {
+        assert!(fit(&[1, 2, 4]).is_degenerate());
+        assert!(fit(&[7]).is_degenerate());
+        assert!(fit(&[0]).is_degenerate());
+        assert!(!fit(&[0, 0]).is_degenerate());
+        assert!(!fit(&[7, 7]).is_degenerate());
+        assert!(fit(&[]).is_degenerate());
+    }
+
+    #[test]
+    fn test_matches_extrapolation() {
+        let history = (0..8).map(|x| 2 * x * x * x - 5 * x * x + 7).collect_vec();
+        let polynomial = fit(&history).polynomial;
+        assert_eq!(polynomial.to_string(), "2*x^3 - 5*x^2 + 7");
+        let table = DifferenceTable::new(&history).unwrap();
+        for x in -5..15 {
+            assert_eq!(
+                polynomial.evaluate(&ratio(x, 1)),
+                ratio(table.value_at(x).unwrap(), 1)
+            );
+        }
+    }
+}
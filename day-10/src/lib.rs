@@ -0,0 +1,2 @@
+pub mod maze;
+pub mod pipe;
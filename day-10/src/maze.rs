@@ -0,0 +1,288 @@
+use crate::pipe::{Direction, Pipe};
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LoopError {
+    NoStart,
+    MultipleStarts(Vec<(i32, i32)>),
+    // Fewer than two neighbours connect back to the start
+    StartUnconnected {
+        connections: usize,
+    },
+    // The walk left the pipe at (x, y) for a tile which does not connect back
+    Broken {
+        x: i32,
+        y: i32,
+        direction: Direction,
+    },
+    // Several pipes under the start would each close a loop
+    Ambiguous(Vec<Pipe>),
+}
+
+impl fmt::Display for LoopError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoopError::NoStart => write!(f, "No start tile"),
+            LoopError::MultipleStarts(starts) => write!(f, "{} start tiles", starts.len()),
+            LoopError::StartUnconnected { connections } => write!(
+                f,
+                "The start tile connects to {connections} neighbours, a loop needs 2"
+            ),
+            LoopError::Broken { x, y, direction } => write!(
+                f,
+                "The pipe at ({x}, {y}) leads {direction:?} to a tile which does not connect back"
+            ),
+            LoopError::Ambiguous(pipes) => write!(
+                f,
+                "The start tile could be any of {}",
+                pipes
+                    .iter()
+                    .map(|p| p.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LoopError {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid {
+    pub width: i32,
+    pub height: i32,
+    pub pipes: Vec<Pipe>,
+}
+
+// The tiles of the loop in walking order, beginning at the start tile
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Loop {
+    pub start_pipe: Pipe,
+    pub tiles: Vec<(i32, i32)>,
+}
+
+impl Loop {
+    // Steps to the tile farthest from the start, going either way round
+    pub fn farthest(&self) -> usize {
+        self.tiles.len() / 2
+    }
+
+    // Tiles enclosed by the loop, from the shoelace formula and Pick's theorem
+    pub fn enclosed(&self) -> usize {
+        let twice_area = self
+            .tiles
+            .iter()
+            .zip(self.tiles.iter().cycle().skip(1))
+            .map(|((x1, y1), (x2, y2))| *x1 as i64 * *y2 as i64 - *x2 as i64 * *y1 as i64)
+            .sum::<i64>()
+            .unsigned_abs() as usize;
+        (twice_area + 2 - self.tiles.len()) / 2
+    }
+}
+
+impl Grid {
+    pub fn get(&self, x: i32, y: i32) -> Option<Pipe> {
+        if x < 0 || y < 0 || x >= self.width || y >= self.height {
+            return None;
+        }
+        Some(self.pipes[(y * self.width + x) as usize])
+    }
+
+    pub fn start(&self) -> Result<(i32, i32), LoopError> {
+        let starts = (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+            .filter(|(x, y)| self.get(*x, *y) == Some(Pipe::Start))
+            .collect::<Vec<_>>();
+        match starts[..] {
+            [] => Err(LoopError::NoStart),
+            [start] => Ok(start),
+            _ => Err(LoopError::MultipleStarts(starts)),
+        }
+    }
+
+    fn step((x, y): (i32, i32), direction: Direction) -> (i32, i32) {
+        let (dx, dy) = direction.offset();
+        (x + dx, y + dy)
+    }
+
+    // Directions from the start towards neighbours with a pipe pointing back at it
+    fn start_connections(&self, start: (i32, i32)) -> Vec<Direction> {
+        Direction::ALL
+            .into_iter()
+            .filter(|d| {
+                let (x, y) = Grid::step(start, *d);
+                self.get(x, y).is_some_and(|p| p.connects(d.opposite()))
+            })
+            .collect()
+    }
+
+    // Follows the pipes from the start, as if it held `start_pipe`, until it returns there
+    fn walk(&self, start: (i32, i32), start_pipe: Pipe) -> Result<Loop, LoopError> {
+        let mut tiles = vec![start];
+        let mut position = start;
+        let mut direction = start_pipe.connections()[0];
+        // Every tile on the way connects to exactly two others, so the walk can only return
+        // to the tile it started from, and does so within one step per tile
+        loop {
+            let next = Grid::step(position, direction);
+            if next == start {
+                if start_pipe.connects(direction.opposite()) {
+                    return Ok(Loop { start_pipe, tiles });
+                }
+            } else if let Some(exit) = self
+                .get(next.0, next.1)
+                .and_then(|p| p.exit(direction.opposite()))
+            {
+                tiles.push(next);
+                position = next;
+                direction = exit;
+                continue;
+            }
+            return Err(LoopError::Broken {
+                x: position.0,
+                y: position.1,
+                direction,
+            });
+        }
+    }
+
+    // The pipe under the start tile, inferred from the neighbours that connect to it
+    pub fn start_pipe(&self) -> Result<Pipe, LoopError> {
+        Ok(self.find_loop()?.start_pipe)
+    }
+
+    pub fn find_loop(&self) -> Result<Loop, LoopError> {
+        let start = self.start()?;
+        let connections = self.start_connections(start);
+        if connections.len() < 2 {
+            return Err(LoopError::StartUnconnected {
+                connections: connections.len(),
+            });
+        }
+        let mut loops = Vec::new();
+        let mut error = None;
+        for (i, a) in connections.iter().enumerate() {
+            for b in connections[i + 1..].iter() {
+                let pipe = Pipe::from_connections(*a, *b).expect("Expected distinct directions");
+                match self.walk(start, pipe) {
+                    Ok(found) => loops.push(found),
+                    Err(e) => error = error.or(Some(e)),
+                }
+            }
+        }
+        match loops.len() {
+            0 => Err(error.expect("Expected a failed walk")),
+            1 => Ok(loops.pop().unwrap()),
+            _ => Err(LoopError::Ambiguous(
+                loops.iter().map(|l| l.start_pipe).collect(),
+            )),
+        }
+    }
+}
+
+pub fn parse_input(input: &str) -> Grid {
+    let lines = input.lines().collect::<Vec<_>>();
+    let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+    let mut pipes = Vec::with_capacity(width * lines.len());
+    for l in lines.iter() {
+        let row = l.chars().map(Pipe::from).collect::<Vec<_>>();
+        pipes.extend(row.iter());
+        pipes.extend(std::iter::repeat_n(Pipe::Empty, width - row.len()));
+    }
+    Grid {
+        width: width as i32,
+        height: lines.len() as i32,
+        pipes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_loop() {
+        let grid = parse_input("-L|F7\n7S-7|\nL|7||\n-L-J|\nL|-JF");
+        let found = grid.find_loop().unwrap();
+        assert_eq!(found.start_pipe, Pipe::SE);
+        assert_eq!(
+            found.tiles,
+            vec![
+                (1, 1),
+                (1, 2),
+                (1, 3),
+                (2, 3),
+                (3, 3),
+                (3, 2),
+                (3, 1),
+                (2, 1)
+            ]
+        );
+        assert_eq!(found.farthest(), 4);
+        assert_eq!(found.enclosed(), 1);
+        let grid = parse_input("..F7.\n.FJ|.\nSJ.L7\n|F--J\nLJ...");
+        assert_eq!(grid.start_pipe(), Ok(Pipe::SE));
+        assert_eq!(grid.find_loop().unwrap().farthest(), 8);
+    }
+
+    #[test]
+    fn test_start_without_east_neighbour() {
+        // The start is on the east edge, so only its north and south neighbours can connect
+        let grid = parse_input("F7\n|S\nLJ");
+        assert_eq!(grid.start_pipe(), Ok(Pipe::NS));
+        let grid = parse_input(".F7\n.||\n.LJ");
+        assert_eq!(grid.find_loop(), Err(LoopError::NoStart));
+    }
+
+    #[test]
+    fn test_enclosed() {
+        let input = ".F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...";
+        assert_eq!(parse_input(input).find_loop().unwrap().enclosed(), 8);
+        let input = "FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L";
+        assert_eq!(parse_input(input).find_loop().unwrap().enclosed(), 10);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            parse_input("S.S").find_loop(),
+            Err(LoopError::MultipleStarts(vec![(0, 0), (2, 0)]))
+        );
+        assert_eq!(
+            parse_input("S-.").find_loop(),
+            Err(LoopError::StartUnconnected { connections: 1 })
+        );
+        // The pipe south of the start turns away from the rest of the loop
+        assert_eq!(
+            parse_input("S7\n|J\nL.").find_loop(),
+            Err(LoopError::Broken {
+                x: 0,
+                y: 2,
+                direction: Direction::East
+            })
+        );
+        // Two loops meet at the start, so it could be either corner
+        assert_eq!(
+            parse_input("F7..\nLS-7\n.|.|\n.L-J").find_loop(),
+            Err(LoopError::Ambiguous(vec![Pipe::NW, Pipe::SE]))
+        );
+    }
+}
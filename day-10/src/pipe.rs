@@ -0,0 +1,140 @@
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::East => Direction::West,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East,
+        }
+    }
+
+    // y grows southwards, as in the input
+    pub fn offset(self) -> (i32, i32) {
+        match self {
+            Direction::North => (0, -1),
+            Direction::East => (1, 0),
+            Direction::South => (0, 1),
+            Direction::West => (-1, 0),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Pipe {
+    Start,
+    WE,
+    NS,
+    NE,
+    SE,
+    SW,
+    NW,
+    Empty,
+}
+
+impl Pipe {
+    pub const CONNECTORS: [Pipe; 6] = [Pipe::WE, Pipe::NS, Pipe::NE, Pipe::SE, Pipe::SW, Pipe::NW];
+
+    // The start tile hides its pipe, so like an empty tile it declares no connections
+    pub fn connections(self) -> &'static [Direction] {
+        match self {
+            Pipe::WE => &[Direction::West, Direction::East],
+            Pipe::NS => &[Direction::North, Direction::South],
+            Pipe::NE => &[Direction::North, Direction::East],
+            Pipe::SE => &[Direction::South, Direction::East],
+            Pipe::SW => &[Direction::South, Direction::West],
+            Pipe::NW => &[Direction::North, Direction::West],
+            Pipe::Start | Pipe::Empty => &[],
+        }
+    }
+
+    pub fn connects(self, direction: Direction) -> bool {
+        self.connections().contains(&direction)
+    }
+
+    pub fn from_connections(a: Direction, b: Direction) -> Option<Pipe> {
+        Pipe::CONNECTORS
+            .into_iter()
+            .find(|p| a != b && p.connects(a) && p.connects(b))
+    }
+
+    // Leaving through the other end after entering from `from`
+    pub fn exit(self, from: Direction) -> Option<Direction> {
+        match self.connections() {
+            [a, b] if *a == from => Some(*b),
+            [a, b] if *b == from => Some(*a),
+            _ => None,
+        }
+    }
+}
+
+impl From<char> for Pipe {
+    fn from(c: char) -> Self {
+        match c {
+            'S' => Pipe::Start,
+            '|' => Pipe::NS,
+            '-' => Pipe::WE,
+            'L' => Pipe::NE,
+            'J' => Pipe::NW,
+            '7' => Pipe::SW,
+            'F' => Pipe::SE,
+            _ => Pipe::Empty,
+        }
+    }
+}
+
+impl fmt::Display for Pipe {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let c = match self {
+            Pipe::Start => 'S',
+            Pipe::NS => '|',
+            Pipe::WE => '-',
+            Pipe::NE => 'L',
+            Pipe::NW => 'J',
+            Pipe::SW => '7',
+            Pipe::SE => 'F',
+            Pipe::Empty => '.',
+        };
+        write!(f, "{c}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connections() {
+        for pipe in Pipe::CONNECTORS {
+            let [a, b] = pipe.connections() else {
+                panic!("Expected two connections for {pipe}");
+            };
+            assert_eq!(Pipe::from_connections(*a, *b), Some(pipe));
+            assert_eq!(Pipe::from_connections(*b, *a), Some(pipe));
+            assert_eq!(pipe.exit(*a), Some(*b));
+            assert_eq!(Pipe::from(pipe.to_string().chars().next().unwrap()), pipe);
+        }
+        assert_eq!(
+            Pipe::from_connections(Direction::North, Direction::North),
+            None
+        );
+        assert_eq!(Pipe::NE.exit(Direction::South), None);
+        assert_eq!(Pipe::Start.exit(Direction::South), None);
+    }
+}
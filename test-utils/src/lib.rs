@@ -0,0 +1,82 @@
+use std::{
+    fmt::Debug,
+    panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
+};
+
+// Small deterministic generator, so property tests need no extra dependency
+pub struct Lcg(u64);
+
+impl Lcg {
+    pub fn new(seed: u64) -> Self {
+        Lcg(seed)
+    }
+
+    // A value in `0..bound`
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (self.0 >> 33) % bound
+    }
+
+    // A value in `lo..hi`
+    pub fn range(&mut self, lo: i64, hi: i64) -> i64 {
+        lo + self.below((hi - lo) as u64) as i64
+    }
+
+    // True one time in `n`
+    pub fn one_in(&mut self, n: u64) -> bool {
+        self.below(n) == 0
+    }
+}
+
+// Checks `property` against `cases` inputs built by `generate`, and names the failing input
+pub fn for_all<T: Debug>(
+    seed: u64,
+    cases: usize,
+    mut generate: impl FnMut(&mut Lcg) -> T,
+    mut property: impl FnMut(&T),
+) {
+    let mut rng = Lcg::new(seed);
+    for case in 0..cases {
+        let input = generate(&mut rng);
+        if let Err(e) = catch_unwind(AssertUnwindSafe(|| property(&input))) {
+            eprintln!("Property failed on case {case} of seed {seed}: {input:?}");
+            resume_unwind(e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lcg() {
+        let mut a = Lcg::new(7);
+        let mut b = Lcg::new(7);
+        for _ in 0..100 {
+            let x = a.range(-3, 4);
+            assert_eq!(x, b.range(-3, 4));
+            assert!((-3..4).contains(&x));
+        }
+        let mut count = 0;
+        for_all(
+            1,
+            50,
+            |rng| rng.below(10),
+            |x| {
+                assert!(*x < 10);
+                count += 1;
+            },
+        );
+        assert_eq!(count, 50);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_for_all_fails() {
+        for_all(1, 50, |rng| rng.below(10), |x| assert!(*x < 9));
+    }
+}